@@ -0,0 +1,130 @@
+//! Derive macros for [ra-evaluator](https://crates.io/crates/ra-evaluator).
+//!
+//! These are re-exported by ``ra_evaluator`` and should be used from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Derives ``Relation`` for a struct with named fields, along with a ``Column``
+/// constant for each field named after the field in upper case.
+#[proc_macro_derive(Relation)]
+pub fn derive_relation(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_relation(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_relation(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &data.fields,
+                    "Relation can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Relation can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let name_string = name.to_string();
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents: Vec<_> = fields
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .collect();
+    let column_names: Vec<_> = idents
+        .iter()
+        .map(|ident| ident.unraw().to_string())
+        .collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let type_names: Vec<_> = types.iter().map(|ty| type_name(ty)).collect();
+    let visibilities: Vec<_> = fields.iter().map(|field| &field.vis).collect();
+    let constants: Vec<_> = column_names
+        .iter()
+        .map(|column_name| format_ident!("{}", column_name.to_uppercase()))
+        .collect();
+    let docs: Vec<_> = column_names
+        .iter()
+        .map(|column_name| format!("The ``{}`` column.", column_name))
+        .collect();
+
+    Ok(quote! {
+        impl #impl_generics ::ra_evaluator::Relation for #name #type_generics #where_clause {
+            const SCHEMA: ::ra_evaluator::Schema = ::ra_evaluator::Schema {
+                name: #name_string,
+                columns: &[
+                    #(::ra_evaluator::ColumnInfo {
+                        name: #column_names,
+                        type_name: #type_names,
+                    }),*
+                ],
+            };
+
+            type Tuple = (#(#types,)*);
+
+            fn into_tuple(self) -> Self::Tuple {
+                (#(self.#idents,)*)
+            }
+
+            fn from_tuple(tuple: Self::Tuple) -> Self {
+                let (#(#idents,)*) = tuple;
+
+                Self { #(#idents),* }
+            }
+        }
+
+        impl #impl_generics #name #type_generics #where_clause {
+            #(
+                #[doc = #docs]
+                #visibilities const #constants: ::ra_evaluator::Column<Self, #types> =
+                    ::ra_evaluator::Column::new(#column_names, |row| &row.#idents);
+            )*
+        }
+    })
+}
+
+/// Writes ``ty`` as it would usually be written in source, such as ``Option<u32>``
+/// rather than the ``Option < u32 >`` produced by the token stream.
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let mut name = String::with_capacity(tokens.len());
+    let mut chars = tokens.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => {
+                let previous = name.chars().last().unwrap_or(' ');
+                let next = chars.peek().copied().unwrap_or(' ');
+
+                if is_word(previous) && is_word(next) {
+                    name.push(' ');
+                }
+            }
+            ',' | ';' => {
+                name.push(c);
+                name.push(' ');
+            }
+            _ => name.push(c),
+        }
+    }
+
+    name.trim_end().to_string()
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
@@ -0,0 +1,266 @@
+use std::hash::{Hash, Hasher};
+use std::ops::AddAssign;
+use std::sync::Arc;
+
+/// Defines an aggregate function that folds the tuples of a group into a single value.
+///
+/// Evaluation calls ``init`` once per group, ``accumulate`` once for each tuple in
+/// the group and finally ``finish`` to produce the aggregated value.
+///
+/// Aggregators can be combined by placing them in a tuple, in which case the
+/// output is the tuple of the individual outputs.
+pub trait Aggregator<S>: Clone {
+    type State;
+    type Output: Clone + Eq + PartialEq;
+
+    fn init(&self) -> Self::State;
+
+    fn accumulate(&self, state: &mut Self::State, row: &S);
+
+    fn finish(&self, state: Self::State) -> Self::Output;
+}
+
+/// Counts the number of tuples in each group.
+#[derive(Clone, Copy, Debug)]
+pub struct Count;
+
+impl<S> Aggregator<S> for Count {
+    type State = usize;
+    type Output = usize;
+
+    fn init(&self) -> usize {
+        0
+    }
+
+    fn accumulate(&self, state: &mut usize, _: &S) {
+        *state += 1;
+    }
+
+    fn finish(&self, state: usize) -> usize {
+        state
+    }
+}
+
+/// Sums the values produced by ``mapper`` for each tuple in a group.
+#[derive(Clone)]
+pub struct Sum<S, T> {
+    pub mapper: Arc<dyn Fn(&S) -> T + Send + Sync>,
+}
+
+impl<S, T> Sum<S, T> {
+    pub fn new(mapper: impl Fn(&S) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T> Aggregator<S> for Sum<S, T>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq + Default + AddAssign,
+{
+    type State = T;
+    type Output = T;
+
+    fn init(&self) -> T {
+        T::default()
+    }
+
+    fn accumulate(&self, state: &mut T, row: &S) {
+        *state += (self.mapper)(row);
+    }
+
+    fn finish(&self, state: T) -> T {
+        state
+    }
+}
+
+/// Finds the smallest value produced by ``mapper`` for each tuple in a group.
+#[derive(Clone)]
+pub struct Min<S, T> {
+    pub mapper: Arc<dyn Fn(&S) -> T + Send + Sync>,
+}
+
+impl<S, T> Min<S, T> {
+    pub fn new(mapper: impl Fn(&S) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T> Aggregator<S> for Min<S, T>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq + Ord,
+{
+    type State = Option<T>;
+    type Output = T;
+
+    fn init(&self) -> Option<T> {
+        None
+    }
+
+    fn accumulate(&self, state: &mut Option<T>, row: &S) {
+        let value = (self.mapper)(row);
+
+        match state {
+            Some(min) if *min <= value => {}
+            _ => *state = Some(value),
+        }
+    }
+
+    fn finish(&self, state: Option<T>) -> T {
+        state.expect("groups always contain at least one tuple")
+    }
+}
+
+/// Finds the largest value produced by ``mapper`` for each tuple in a group.
+#[derive(Clone)]
+pub struct Max<S, T> {
+    pub mapper: Arc<dyn Fn(&S) -> T + Send + Sync>,
+}
+
+impl<S, T> Max<S, T> {
+    pub fn new(mapper: impl Fn(&S) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T> Aggregator<S> for Max<S, T>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq + Ord,
+{
+    type State = Option<T>;
+    type Output = T;
+
+    fn init(&self) -> Option<T> {
+        None
+    }
+
+    fn accumulate(&self, state: &mut Option<T>, row: &S) {
+        let value = (self.mapper)(row);
+
+        match state {
+            Some(max) if *max >= value => {}
+            _ => *state = Some(value),
+        }
+    }
+
+    fn finish(&self, state: Option<T>) -> T {
+        state.expect("groups always contain at least one tuple")
+    }
+}
+
+/// Converts a numeric value to ``f64`` so that it can be averaged by ``Avg``.
+///
+/// Like an ``as`` cast, the conversion may lose precision for large 64 and 128-bit values.
+pub trait AsF64 {
+    fn as_f64(&self) -> f64;
+}
+
+macro_rules! impl_as_f64 {
+    ($($number:ty),+) => {
+        $(
+            impl AsF64 for $number {
+                fn as_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_as_f64!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Averages the values produced by ``mapper`` for each tuple in a group.
+#[derive(Clone)]
+pub struct Avg<S, T> {
+    pub mapper: Arc<dyn Fn(&S) -> T + Send + Sync>,
+}
+
+impl<S, T> Avg<S, T> {
+    pub fn new(mapper: impl Fn(&S) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T> Aggregator<S> for Avg<S, T>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + AsF64,
+{
+    type State = (f64, usize);
+    type Output = Average;
+
+    fn init(&self) -> (f64, usize) {
+        (0.0, 0)
+    }
+
+    fn accumulate(&self, state: &mut (f64, usize), row: &S) {
+        state.0 += (self.mapper)(row).as_f64();
+        state.1 += 1;
+    }
+
+    fn finish(&self, state: (f64, usize)) -> Average {
+        Average(state.0 / state.1 as f64)
+    }
+}
+
+/// The result of an ``Avg`` aggregate.
+///
+/// Tuples in a relation must be comparable for equality, which ``f64`` is not,
+/// so averages are wrapped in a type that compares by bit pattern instead.
+#[derive(Clone, Copy, Debug)]
+pub struct Average(pub f64);
+
+impl PartialEq for Average {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Average {}
+
+impl Hash for Average {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+macro_rules! impl_aggregator_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<S, $($name),+> Aggregator<S> for ($($name,)+)
+        where
+            $($name: Aggregator<S>),+
+        {
+            type State = ($($name::State,)+);
+            type Output = ($($name::Output,)+);
+
+            fn init(&self) -> Self::State {
+                ($(self.$index.init(),)+)
+            }
+
+            fn accumulate(&self, state: &mut Self::State, row: &S) {
+                $(self.$index.accumulate(&mut state.$index, row);)+
+            }
+
+            fn finish(&self, state: Self::State) -> Self::Output {
+                ($(self.$index.finish(state.$index),)+)
+            }
+        }
+    };
+}
+
+impl_aggregator_for_tuple!(A 0);
+impl_aggregator_for_tuple!(A 0, B 1);
+impl_aggregator_for_tuple!(A 0, B 1, C 2);
+impl_aggregator_for_tuple!(A 0, B 1, C 2, D 3);
+impl_aggregator_for_tuple!(A 0, B 1, C 2, D 3, F 4);
+impl_aggregator_for_tuple!(A 0, B 1, C 2, D 3, F 4, G 5);
@@ -0,0 +1,142 @@
+use crate::parallel::{par_chunks, par_join};
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Keeps the tuples in ``left_expression`` that match no tuple in
+/// ``right_expression`` according to ``predicate``.
+///
+/// This is the complement of ``SemiJoin`` and likewise keeps the type of ``left_expression``.
+#[derive(Clone)]
+pub struct AntiJoin<L, R, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> bool + Send + Sync>,
+    phantom: PhantomData<R>,
+}
+
+impl<L, R, EL, ER> AntiJoin<L, R, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<L, R, EL, ER> Expression<L> for AntiJoin<L, R, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<L> {
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<L>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+
+        Ok(left_result
+            .into_iter()
+            .filter(|row1| !right_result.iter().any(|row2| (self.predicate)(row1, row2)))
+            .collect())
+    }
+
+    /// Buffers ``right_expression`` and streams ``left_expression`` against it.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = L> + 'a>
+    where
+        L: 'a,
+    {
+        let right_result = self.right_expression.eval();
+
+        Box::new(
+            self.left_expression
+                .iter()
+                .filter(move |row1| !right_result.iter().any(|row2| (self.predicate)(row1, row2))),
+        )
+    }
+}
+
+impl<L, R, EL, ER> ParallelExpression<L> for AntiJoin<L, R, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently, then partitions ``left_expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<L> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        par_chunks(left_result, threads, |rows| {
+            rows.into_iter()
+                .filter(|row1| !right_result.iter().any(|row2| (self.predicate)(row1, row2)))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn keep_unmatched_rows() {
+        let values1 = &[(1, "test string", 123), (2, "another string", 25)];
+        let values2 = &[("a", 2), ("b", 3)];
+
+        assert_eq!(
+            AntiJoin::new(Terminal::new(values1), Terminal::new(values2), |x, y| x.0 == y.1).eval(),
+            &[(1, "test string", 123)]
+        );
+    }
+
+    #[test]
+    fn all_rows_matched() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[1, 1, 2];
+
+        assert_eq!(
+            AntiJoin::new(Terminal::new(values1), Terminal::new(values2), |x, y| x.0 == *y).eval(),
+            &[]
+        );
+    }
+
+    #[test]
+    fn empty_right() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2: &[i32] = &[];
+
+        assert_eq!(
+            AntiJoin::new(Terminal::new(values1), Terminal::new(values2), |_, _| true).eval(),
+            values1
+        );
+    }
+}
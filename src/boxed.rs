@@ -0,0 +1,164 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::sync::Arc;
+
+/// An object-safe counterpart of ``Expression``, implemented for every
+/// expression that can be shared between threads.
+///
+/// Its methods are named differently from those of ``Expression`` so that the
+/// two traits can be in scope together. Most code should use ``BoxedExpression``
+/// rather than this trait directly.
+pub trait DynExpression<Output>: Send + Sync
+where
+    Output: Clone + Eq + PartialEq,
+{
+    fn dyn_eval(&self) -> Vec<Output>;
+
+    fn dyn_try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Output>, EvalError>;
+
+    fn dyn_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Output> + 'a>
+    where
+        Output: 'a;
+
+    fn dyn_try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<Output, EvalError>> + 'a>
+    where
+        Output: 'a;
+}
+
+impl<Output, E> DynExpression<Output> for E
+where
+    Output: Clone + Eq + PartialEq,
+    E: Expression<Output> + Send + Sync,
+{
+    fn dyn_eval(&self) -> Vec<Output> {
+        self.eval()
+    }
+
+    fn dyn_try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Output>, EvalError> {
+        self.try_eval_with_context(context)
+    }
+
+    fn dyn_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Output> + 'a>
+    where
+        Output: 'a,
+    {
+        self.iter()
+    }
+
+    fn dyn_try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<Output, EvalError>> + 'a>
+    where
+        Output: 'a,
+    {
+        self.try_iter_with_context(context)
+    }
+}
+
+/// A type-erased ``expression`` whose type depends only on the tuples it produces.
+///
+/// Plans of different shapes can be stored in the same struct field or ``Vec``,
+/// or chosen at runtime, once boxed. Clones share the same expression.
+#[derive(Clone)]
+pub struct BoxedExpression<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    pub expression: Arc<dyn DynExpression<S>>,
+}
+
+impl<S> BoxedExpression<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    pub fn new<E>(expression: E) -> Self
+    where
+        E: Expression<S> + Send + Sync + 'static,
+    {
+        Self {
+            expression: Arc::new(expression),
+        }
+    }
+}
+
+impl<S> Expression<S> for BoxedExpression<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn eval(&self) -> Vec<S> {
+        self.expression.dyn_eval()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        self.expression.dyn_try_eval_with_context(context)
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        self.expression.dyn_iter()
+    }
+
+    fn try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<S, EvalError>> + 'a>
+    where
+        S: 'a,
+    {
+        self.expression.dyn_try_iter_with_context(context)
+    }
+}
+
+impl<S> ParallelExpression<S> for BoxedExpression<S>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Projection, Selection, Terminal, TrySelection, Union};
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn plans_of_different_types() {
+        let values = &[1, 2, 3, 4];
+
+        let plans: Vec<BoxedExpression<i32>> = vec![
+            BoxedExpression::new(Terminal::new(values)),
+            BoxedExpression::new(Selection::new(Terminal::new(values), |x| x % 2 == 0)),
+            BoxedExpression::new(Projection::new(Terminal::new(values), |x| x * 10)),
+        ];
+
+        assert_eq!(
+            plans.iter().map(Expression::eval).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3, 4], vec![2, 4], vec![10, 20, 30, 40]]
+        );
+        assert_eq!(
+            Union::new(plans[1].clone(), plans[2].clone()).iter().collect::<Vec<_>>(),
+            &[2, 4, 10, 20, 30, 40]
+        );
+    }
+
+    #[test]
+    fn errors_pass_through() {
+        let values = &["1", "a"];
+
+        let boxed = BoxedExpression::new(TrySelection::new(Terminal::new(values), |x| {
+            Ok::<_, ParseIntError>(x.parse::<i32>()? > 0)
+        }));
+
+        let error = boxed.try_eval().unwrap_err();
+
+        assert_eq!(error.operator, "TrySelection");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{EvalContext, EvalError, Expression, MissingParams, ParallelExpression};
+
+use std::convert::Infallible;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// A shared counter identifying the current version of some underlying data.
+///
+/// Owners of the data should call ``bump`` whenever it changes so that any
+/// ``Cached`` expressions tracking the version recompute on their next evaluation.
+/// Clones share the same counter.
+#[derive(Clone, Debug, Default)]
+pub struct DataVersion(Arc<AtomicU64>);
+
+impl DataVersion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    pub fn bump(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Memoises the result of ``expression`` so that it is only computed once.
+///
+/// The cached result is shared between all clones of the expression, so a chain
+/// built on top of a ``Cached`` expression can be cloned and evaluated repeatedly
+/// without recomputing the cached subtree. The result is recomputed after an
+/// explicit call to ``invalidate`` or when the tracked ``DataVersion`` changes.
+///
+/// Results are cached even when parameters are bound, as long as the cached
+/// subtree does not read them. A subtree that reads parameters is detected on
+/// its first parameterised evaluation and bypasses the cache from then on.
+#[derive(Clone)]
+pub struct Cached<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub version: Option<DataVersion>,
+    cache: Arc<RwLock<Option<(u64, Vec<S>)>>>,
+    reads_params: Arc<AtomicBool>,
+}
+
+impl<S, E> Cached<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub fn new(expression: E) -> Self {
+        Self {
+            expression,
+            version: None,
+            cache: Arc::new(RwLock::new(None)),
+            reads_params: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_version(expression: E, version: DataVersion) -> Self {
+        Self {
+            version: Some(version),
+            ..Self::new(expression)
+        }
+    }
+
+    /// Discards the cached result, forcing the next evaluation to recompute it.
+    pub fn invalidate(&self) {
+        *self.cache.write().unwrap() = None;
+    }
+
+    /// Returns the cached result if it exists and is up to date, otherwise
+    /// computes it with ``compute`` and stores it. Errors are not cached.
+    fn get_or_compute<Err>(
+        &self,
+        compute: impl FnOnce() -> Result<Vec<S>, Err>,
+    ) -> Result<Vec<S>, Err> {
+        let version = self.version.as_ref().map_or(0, DataVersion::get);
+
+        if let Some((cached_version, rows)) = &*self.cache.read().unwrap() {
+            if *cached_version == version {
+                return Ok(rows.clone());
+            }
+        }
+
+        let rows = compute()?;
+        *self.cache.write().unwrap() = Some((version, rows.clone()));
+        Ok(rows)
+    }
+}
+
+impl<S, E> Expression<S> for Cached<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        let Ok(rows) = self.get_or_compute(|| Ok::<_, Infallible>(self.expression.eval()));
+        rows
+    }
+
+    /// The subtree is first evaluated without parameters so that its result can be
+    /// cached. Only if it turns out to read them is it evaluated with ``context``.
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        if context.params.is_none() || !self.reads_params.load(Ordering::Acquire) {
+            let without_params = EvalContext {
+                params: None,
+                ..context.clone()
+            };
+
+            match self.get_or_compute(|| self.expression.try_eval_with_context(&without_params)) {
+                Err(error) if context.params.is_some() && error.source.is::<MissingParams>() => {
+                    self.reads_params.store(true, Ordering::Release);
+                }
+                result => return result,
+            }
+        }
+
+        self.expression.try_eval_with_context(context)
+    }
+}
+
+impl<S, E> ParallelExpression<S> for Cached<S, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let Ok(rows) =
+            self.get_or_compute(|| Ok::<_, Infallible>(self.expression.par_eval(threads)));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ParamSelection, Projection, Terminal};
+
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn computes_once() {
+        static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let cached = Cached::new(Projection::new(Terminal::new(&[1, 2, 3]), |x| {
+            EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        }));
+
+        assert_eq!(cached.eval(), &[2, 4, 6]);
+        assert_eq!(cached.clone().eval(), &[2, 4, 6]);
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn explicit_invalidation() {
+        static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let cached = Cached::new(Projection::new(Terminal::new(&[1, 2, 3]), |x| {
+            EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        }));
+
+        cached.eval();
+        cached.clone().invalidate();
+        assert_eq!(cached.eval(), &[2, 4, 6]);
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn version_change_invalidates() {
+        static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let version = DataVersion::new();
+        let cached = Cached::with_version(
+            Projection::new(Terminal::new(&[1, 2, 3]), |x| {
+                EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+                x * 2
+            }),
+            version.clone(),
+        );
+
+        cached.eval();
+        cached.eval();
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 3);
+
+        version.bump();
+        cached.eval();
+        cached.eval();
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn param_free_subtree_cached_across_bindings() {
+        static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let cached = Cached::new(Projection::new(Terminal::new(&[1, 2, 3]), |x| {
+            EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+            x * 2
+        }));
+        let plan = ParamSelection::new(cached, |x, min: &i32| x >= min);
+
+        assert_eq!(plan.eval_with(&4), &[4, 6]);
+        assert_eq!(plan.eval_with(&6), &[6]);
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn parameterised_results_not_cached() {
+        let values = &[1, 2, 3];
+        let cached = Cached::new(ParamSelection::new(Terminal::new(values), |x, min: &i32| {
+            x >= min
+        }));
+
+        assert_eq!(cached.eval_with(&2), &[2, 3]);
+        assert_eq!(cached.eval_with(&3), &[3]);
+    }
+}
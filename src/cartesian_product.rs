@@ -1,4 +1,9 @@
-use crate::{Expression, Join};
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, Join,
+    ParallelExpression,
+};
+
+use std::hash::Hash;
 
 /// Produces the unconditional join of ``left_expression`` and ``right_expression``.
 #[derive(Clone)]
@@ -21,7 +26,11 @@ where
     EL: Expression<L>,
     ER: Expression<R>,
 {
-    pub fn new(left_expression: EL, right_expression: ER, mapper: fn(&L, &R) -> Res) -> Self {
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        mapper: impl Fn(&L, &R) -> Res + Send + Sync + 'static,
+    ) -> Self {
         Self {
             joiner: Join::new(left_expression, right_expression, |_, _| true, mapper),
         }
@@ -39,12 +48,64 @@ where
     fn eval(&self) -> Vec<Res> {
         self.joiner.eval()
     }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        self.joiner.combine(
+            &self.joiner.left_expression.try_eval_with_context(context)?,
+            &self.joiner.right_expression.try_eval_with_context(context)?,
+            context,
+            "CartesianProduct",
+        )
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Res> + 'a>
+    where
+        Res: 'a,
+    {
+        self.joiner.iter()
+    }
+}
+
+impl<L, R, Res, EL, ER> ParallelExpression<Res> for CartesianProduct<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        self.joiner.par_eval(threads)
+    }
+}
+
+impl<L, R, Res, EL, ER> IncrementalExpression<Res> for CartesianProduct<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Hash,
+    R: Clone + Eq + PartialEq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: IncrementalExpression<L>,
+    ER: IncrementalExpression<R>,
+{
+    type State = <Join<L, R, Res, EL, ER> as IncrementalExpression<Res>>::State;
+
+    fn init(&self) -> Self::State {
+        self.joiner.init()
+    }
+
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<Res> {
+        self.joiner.delta(state, changes)
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.joiner.apply(changes);
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Terminal;
+    use crate::{LimitExceeded, Terminal};
 
     #[test]
     fn cartesian_product_homogenous_types() {
@@ -87,4 +148,22 @@ mod test {
             expected_result
         );
     }
+
+    #[test]
+    fn cartesian_product_past_deadline() {
+        let values = &[1, 2];
+        let context = EvalContext::new().with_deadline(std::time::Instant::now());
+
+        let error = CartesianProduct::new(Terminal::new(values), Terminal::new(values), |x, y| {
+            (*x, *y)
+        })
+        .try_eval_with_context(&context)
+        .unwrap_err();
+
+        assert_eq!(error.operator, "CartesianProduct");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Deadline)
+        );
+    }
 }
@@ -0,0 +1,188 @@
+use crate::parallel::par_join;
+use crate::{EvalContext, EvalError, Expression, ParallelExpression, Semantics};
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Keeps only the tuples in ``left_expression`` that are not in ``right_expression``.
+///
+/// Under bag semantics (``EXCEPT ALL``) each tuple in ``right_expression`` removes
+/// at most one matching tuple from ``left_expression``, so a tuple occurring ``m``
+/// times on the left and ``n`` times on the right appears ``max(m - n, 0)`` times.
+/// Under set semantics (``EXCEPT``) the result contains no duplicates.
+#[derive(Clone)]
+pub struct Difference<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<S>,
+{
+    pub left_expression: E1,
+    pub right_expression: E2,
+    pub semantics: Semantics,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E1, E2> Difference<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<S>,
+{
+    pub fn new(left_expression: E1, right_expression: E2) -> Self {
+        Self::with_semantics(left_expression, right_expression, Semantics::Bag)
+    }
+
+    pub fn with_semantics(left_expression: E1, right_expression: E2, semantics: Semantics) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            semantics,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Filters the tuples of ``left_rows`` against the evaluated ``right_result``.
+    fn combine<'a>(
+        &self,
+        left_rows: impl Iterator<Item = S> + 'a,
+        right_result: Vec<S>,
+    ) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        match self.semantics {
+            Semantics::Bag => {
+                let mut counts: HashMap<S, usize> = HashMap::new();
+                for row in right_result {
+                    *counts.entry(row).or_insert(0) += 1;
+                }
+
+                Box::new(left_rows.filter(move |row| match counts.get_mut(row) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }))
+            }
+            Semantics::Set => {
+                let mut excluded: HashSet<S> = right_result.into_iter().collect();
+
+                Box::new(left_rows.filter(move |row| excluded.insert(row.clone())))
+            }
+        }
+    }
+}
+
+impl<S, E1, E2> Expression<S> for Difference<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let left_result = (self.left_expression).try_eval_with_context(context)?;
+        let right_result = (self.right_expression).try_eval_with_context(context)?;
+
+        Ok(self.combine(left_result.into_iter(), right_result).collect())
+    }
+
+    /// Buffers ``right_expression`` and streams ``left_expression`` against it.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        self.combine(self.left_expression.iter(), (self.right_expression).eval())
+    }
+}
+
+impl<S, E1, E2> ParallelExpression<S> for Difference<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash + Send + Sync,
+    E1: ParallelExpression<S>,
+    E2: ParallelExpression<S>,
+{
+    /// Evaluates both sides concurrently.
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        self.combine(left_result.into_iter(), right_result)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn difference_right_identity() {
+        let values = &[(1, "test string", 123), (2, "another string", 25)];
+
+        assert_eq!(
+            Difference::new(Terminal::new(values), Terminal::new(&[])).eval(),
+            values
+        );
+    }
+
+    #[test]
+    fn difference_left_empty() {
+        let values = &[(1, "test string", 123), (2, "another string", 25)];
+
+        assert_eq!(
+            Difference::new(Terminal::new(&[]), Terminal::new(values)).eval(),
+            &[]
+        );
+    }
+
+    #[test]
+    fn difference_some() {
+        let values1 = &[
+            (1, "test string", 123),
+            (2, "another string", 25),
+            (3, "test string", 123),
+        ];
+        let values2 = &[(2, "another string", 25), (4, "another string", 25)];
+
+        let expected_result = &[(1, "test string", 123), (3, "test string", 123)];
+
+        assert_eq!(
+            Difference::new(Terminal::new(values1), Terminal::new(values2)).eval(),
+            expected_result
+        );
+    }
+
+    #[test]
+    fn difference_bag_subtracts_multiplicities() {
+        let values1 = &[1, 1, 1, 2, 2, 3];
+        let values2 = &[1, 2, 2, 2, 4];
+
+        assert_eq!(
+            Difference::new(Terminal::new(values1), Terminal::new(values2)).eval(),
+            &[1, 1, 3]
+        );
+    }
+
+    #[test]
+    fn difference_set_removes_duplicates() {
+        let values1 = &[1, 1, 1, 2, 2, 3, 3];
+        let values2 = &[2, 4];
+
+        assert_eq!(
+            Difference::with_semantics(Terminal::new(values1), Terminal::new(values2), Semantics::Set)
+                .eval(),
+            &[1, 3]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Removes duplicate tuples from ``expression``.
+///
+/// This converts the bag produced by ``expression`` into a set, keeping the
+/// first occurrence of each tuple in its original position.
+#[derive(Clone)]
+pub struct Distinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+{
+    pub expression: E,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E> Distinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+{
+    pub fn new(expression: E) -> Self {
+        Self {
+            expression,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, E> Expression<S> for Distinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut seen = HashSet::new();
+
+        Ok(self
+            .expression
+            .try_eval_with_context(context)?
+            .into_iter()
+            .filter(|row| seen.insert(row.clone()))
+            .collect())
+    }
+
+    /// Streams ``expression``, buffering only the distinct tuples seen so far.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+
+        Box::new(
+            self.expression
+                .iter()
+                .filter(move |row| seen.insert(row.clone())),
+        )
+    }
+}
+
+impl<S, E> ParallelExpression<S> for Distinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let mut seen = HashSet::new();
+
+        self.expression
+            .par_eval(threads)
+            .into_iter()
+            .filter(|row| seen.insert(row.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn no_duplicates() {
+        let values = &[(1, "test string", 123), (2, "another string", 25)];
+
+        assert_eq!(Distinct::new(Terminal::new(values)).eval(), values);
+    }
+
+    #[test]
+    fn remove_duplicates() {
+        let values = &[3, 1, 3, 2, 1, 3];
+
+        assert_eq!(Distinct::new(Terminal::new(values)).eval(), &[3, 1, 2]);
+    }
+
+    #[test]
+    fn empty_relation() {
+        let values: &[i32] = &[];
+
+        assert_eq!(Distinct::new(Terminal::new(values)).eval(), &[]);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::parallel::par_join;
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Finds the tuples ``q`` such that ``(q, d)`` is in ``dividend_expression`` for
+/// every tuple ``d`` in ``divisor_expression``.
+///
+/// Each dividend tuple is split into its quotient attributes by ``quotient_mapper``
+/// and its divisor attributes by ``divisor_mapper``. The classic example is finding
+/// the suppliers that supply every part, where the dividend relates suppliers to
+/// parts and the divisor lists the parts.
+///
+/// The result uses set semantics and quotients appear in the order they are first
+/// encountered in ``dividend_expression``.
+#[derive(Clone)]
+pub struct Division<S, D, Q, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    pub dividend_expression: E1,
+    pub divisor_expression: E2,
+    pub quotient_mapper: Arc<dyn Fn(&S) -> Q + Send + Sync>,
+    pub divisor_mapper: Arc<dyn Fn(&S) -> D + Send + Sync>,
+}
+
+impl<S, D, Q, E1, E2> Division<S, D, Q, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    pub fn new(
+        dividend_expression: E1,
+        divisor_expression: E2,
+        quotient_mapper: impl Fn(&S) -> Q + Send + Sync + 'static,
+        divisor_mapper: impl Fn(&S) -> D + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            dividend_expression,
+            divisor_expression,
+            quotient_mapper: Arc::new(quotient_mapper),
+            divisor_mapper: Arc::new(divisor_mapper),
+        }
+    }
+}
+
+impl<S, D, Q, E1, E2> Expression<Q> for Division<S, D, Q, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    fn eval(&self) -> Vec<Q> {
+        self.combine(
+            &self.dividend_expression.eval(),
+            self.divisor_expression.eval(),
+        )
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Q>, EvalError> {
+        Ok(self.combine(
+            &self.dividend_expression.try_eval_with_context(context)?,
+            self.divisor_expression.try_eval_with_context(context)?,
+        ))
+    }
+}
+
+impl<S, D, Q, E1, E2> ParallelExpression<Q> for Division<S, D, Q, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    D: Clone + Eq + PartialEq + Hash + Send + Sync,
+    Q: Clone + Eq + PartialEq + Hash + Send + Sync,
+    E1: ParallelExpression<S>,
+    E2: ParallelExpression<D>,
+{
+    /// Evaluates the dividend and divisor concurrently.
+    fn par_eval(&self, threads: usize) -> Vec<Q> {
+        let (dividend_result, divisor_result) = par_join(
+            threads,
+            |threads| self.dividend_expression.par_eval(threads),
+            |threads| self.divisor_expression.par_eval(threads),
+        );
+
+        self.combine(&dividend_result, divisor_result)
+    }
+}
+
+impl<S, D, Q, E1, E2> Division<S, D, Q, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    /// Divides the evaluated dividend by the evaluated divisor.
+    fn combine(&self, dividend_result: &[S], divisor_result: Vec<D>) -> Vec<Q> {
+        let divisor: HashSet<D> = divisor_result.into_iter().collect();
+
+        let mut quotients: Vec<Q> = Vec::new();
+        let mut matched: HashMap<Q, HashSet<D>> = HashMap::new();
+
+        for row in dividend_result {
+            let quotient = (self.quotient_mapper)(row);
+            let remainder = (self.divisor_mapper)(row);
+
+            let divisors = matched.entry(quotient.clone()).or_insert_with(|| {
+                quotients.push(quotient);
+                HashSet::new()
+            });
+
+            if divisor.contains(&remainder) {
+                divisors.insert(remainder);
+            }
+        }
+
+        quotients
+            .into_iter()
+            .filter(|quotient| matched[quotient].len() == divisor.len())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    const SUPPLIES: &[(&str, &str)] = &[
+        ("s1", "p1"),
+        ("s1", "p2"),
+        ("s1", "p3"),
+        ("s1", "p4"),
+        ("s2", "p1"),
+        ("s2", "p2"),
+        ("s3", "p2"),
+        ("s4", "p2"),
+        ("s4", "p4"),
+        ("s4", "p1"),
+    ];
+
+    #[test]
+    fn suppliers_of_all_parts() {
+        let parts = &["p1", "p2", "p3", "p4"];
+
+        assert_eq!(
+            Division::new(Terminal::new(SUPPLIES), Terminal::new(parts), |x| x.0, |x| x.1).eval(),
+            &["s1"]
+        );
+    }
+
+    #[test]
+    fn suppliers_of_some_parts() {
+        let parts = &["p2", "p4"];
+
+        assert_eq!(
+            Division::new(Terminal::new(SUPPLIES), Terminal::new(parts), |x| x.0, |x| x.1).eval(),
+            &["s1", "s4"]
+        );
+    }
+
+    #[test]
+    fn duplicate_dividend_tuples() {
+        let supplies = &[("s1", "p1"), ("s1", "p1"), ("s2", "p1"), ("s2", "p2")];
+        let parts = &["p1", "p2", "p2"];
+
+        assert_eq!(
+            Division::new(Terminal::new(supplies), Terminal::new(parts), |x| x.0, |x| x.1).eval(),
+            &["s2"]
+        );
+    }
+
+    #[test]
+    fn empty_divisor() {
+        let parts: &[&str] = &[];
+
+        assert_eq!(
+            Division::new(Terminal::new(SUPPLIES), Terminal::new(parts), |x| x.0, |x| x.1).eval(),
+            &["s1", "s2", "s3", "s4"]
+        );
+    }
+}
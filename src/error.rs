@@ -0,0 +1,33 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error raised while evaluating an expression.
+///
+/// Records the name of the operator that raised the error along with the
+/// underlying cause, which can be recovered by downcasting ``source``.
+#[derive(Debug)]
+pub struct EvalError {
+    pub operator: &'static str,
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl EvalError {
+    pub fn new(operator: &'static str, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            operator,
+            source: source.into(),
+        }
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.operator, self.source)
+    }
+}
+
+impl Error for EvalError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
@@ -0,0 +1,238 @@
+use crate::EvalError;
+
+use std::any::{self, Any};
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A shared flag used to cancel an evaluation, possibly from another thread.
+///
+/// Clones share the same flag, so a clone can be moved into an ``EvalContext``
+/// while the original is kept to cancel the evaluation.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The reason an evaluation was stopped by its ``EvalContext``.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LimitExceeded {
+    /// An operator produced more than the given number of rows.
+    Rows(usize),
+    Deadline,
+    Cancelled,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Rows(max_rows) => write!(f, "exceeded the limit of {} rows", max_rows),
+            LimitExceeded::Deadline => write!(f, "exceeded the deadline"),
+            LimitExceeded::Cancelled => write!(f, "was cancelled"),
+        }
+    }
+}
+
+impl Error for LimitExceeded {}
+
+/// Raised when an operator expects parameters of a type that were not bound.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MissingParams {
+    pub expected: &'static str,
+}
+
+impl fmt::Display for MissingParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected parameters of type {}", self.expected)
+    }
+}
+
+impl Error for MissingParams {}
+
+/// Bounds the resources an evaluation may use and binds its parameters.
+///
+/// The limits are checked inside the loops of potentially expensive operators
+/// such as Join, CartesianProduct and Intersection. When a limit is exceeded,
+/// evaluation stops with an ``EvalError`` whose source is a ``LimitExceeded``.
+/// The default context has no limits and no parameters.
+#[derive(Clone, Debug, Default)]
+pub struct EvalContext<'a> {
+    pub max_rows: Option<usize>,
+    pub deadline: Option<Instant>,
+    pub cancellation: Option<CancellationToken>,
+    pub params: Option<&'a (dyn Any + Send + Sync)>,
+}
+
+impl<'a> EvalContext<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of rows any single checked operator may produce.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the deadline to ``timeout`` from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Binds ``params`` for parameterised operators such as ParamSelection.
+    pub fn with_params<P>(mut self, params: &'a P) -> Self
+    where
+        P: Any + Send + Sync,
+    {
+        self.params = Some(params);
+        self
+    }
+
+    /// Returns the bound parameters, or an error raised as ``operator`` if no
+    /// parameters of type ``P`` are bound.
+    pub fn params<P>(&self, operator: &'static str) -> Result<&'a P, EvalError>
+    where
+        P: Any,
+    {
+        self.params
+            .and_then(|params| params.downcast_ref())
+            .ok_or_else(|| {
+                EvalError::new(
+                    operator,
+                    MissingParams {
+                        expected: any::type_name::<P>(),
+                    },
+                )
+            })
+    }
+
+    /// Checks that ``operator`` may continue after producing ``rows`` rows.
+    pub fn check(&self, operator: &'static str, rows: usize) -> Result<(), EvalError> {
+        if let Some(cancellation) = &self.cancellation {
+            if cancellation.is_cancelled() {
+                return Err(EvalError::new(operator, LimitExceeded::Cancelled));
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(EvalError::new(operator, LimitExceeded::Deadline));
+            }
+        }
+
+        self.check_rows(operator, rows)
+    }
+
+    /// Checks only the row limit, which is cheap enough to call for every row produced.
+    pub fn check_rows(&self, operator: &'static str, rows: usize) -> Result<(), EvalError> {
+        match self.max_rows {
+            Some(max_rows) if rows > max_rows => {
+                Err(EvalError::new(operator, LimitExceeded::Rows(max_rows)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Passes the tuples of ``rows`` through while ``check`` succeeds for each, letting
+    /// operators that consume their input lazily stop early. The iteration ends at
+    /// the first error produced by ``rows`` or failed check, which is stored in ``status``.
+    pub(crate) fn checked<'b, S>(
+        &'b self,
+        operator: &'static str,
+        rows: impl Iterator<Item = Result<S, EvalError>> + 'b,
+        status: &'b mut Result<(), EvalError>,
+    ) -> impl Iterator<Item = S> + 'b {
+        rows.enumerate().map_while(move |(index, row)| {
+            match row.and_then(|row| self.check(operator, index + 1).map(|()| row)) {
+                Ok(row) => Some(row),
+                Err(error) => {
+                    *status = Err(error);
+                    None
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn reason(result: Result<(), EvalError>) -> LimitExceeded {
+        *result
+            .unwrap_err()
+            .source
+            .downcast_ref::<LimitExceeded>()
+            .unwrap()
+    }
+
+    #[test]
+    fn default_context_is_unlimited() {
+        assert!(EvalContext::new().check("Join", usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn row_limit() {
+        let context = EvalContext::new().with_max_rows(10);
+
+        assert!(context.check("Join", 10).is_ok());
+        assert_eq!(reason(context.check("Join", 11)), LimitExceeded::Rows(10));
+    }
+
+    #[test]
+    fn deadline() {
+        let context = EvalContext::new().with_deadline(Instant::now());
+
+        assert_eq!(reason(context.check("Join", 0)), LimitExceeded::Deadline);
+        assert!(context.check_rows("Join", 0).is_ok());
+    }
+
+    #[test]
+    fn cancellation() {
+        let token = CancellationToken::new();
+        let context = EvalContext::new().with_cancellation(token.clone());
+
+        assert!(context.check("Join", 0).is_ok());
+        token.cancel();
+        assert_eq!(reason(context.check("Join", 0)), LimitExceeded::Cancelled);
+    }
+
+    #[test]
+    fn params() {
+        let context = EvalContext::new().with_params(&42);
+
+        assert_eq!(context.params::<i32>("Selection").unwrap(), &42);
+
+        let error = context.params::<String>("Selection").unwrap_err();
+        assert_eq!(
+            error.source.downcast_ref::<MissingParams>(),
+            Some(&MissingParams {
+                expected: "alloc::string::String"
+            })
+        );
+    }
+}
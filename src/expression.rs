@@ -1,8 +1,11 @@
+use crate::{EvalContext, EvalError};
+
+use std::any::Any;
+
 /// Defines relational algebraic expressions over generic tuple-based relations.
 /// 
 /// Implemented by operations such as Projection, Selection, Join, etc.
 /// Expressions are intended to be recursive and contain sub-expressions.
-
 pub trait Expression<Output>: Clone 
 where
     Output: Clone + Eq + PartialEq,
@@ -10,4 +13,91 @@ where
     /// Calls evaluation on any sub-expressions before performing its 
     /// own transformation and returning the (unwrapped) result.
     fn eval(&self) -> Vec<Output>;
-}
\ No newline at end of file
+
+    /// Evaluates the expression, returning the first error raised by a fallible
+    /// operation such as TrySelection rather than panicking.
+    ///
+    /// Equivalent to ``try_eval_with_context`` with an unlimited ``EvalContext``.
+    fn try_eval(&self) -> Result<Vec<Output>, EvalError> {
+        self.try_eval_with_context(&EvalContext::default())
+    }
+
+    /// Evaluates the expression within the limits of ``context``, returning an error
+    /// as soon as an operator exceeds them.
+    ///
+    /// Expressions with sub-expressions must pass ``context`` on to them.
+    /// The default implementation is suitable for expressions that cannot fail
+    /// and have no sub-expressions.
+    fn try_eval_with_context(&self, _context: &EvalContext) -> Result<Vec<Output>, EvalError> {
+        Ok(self.eval())
+    }
+
+    /// Evaluates the expression with ``params`` bound for parameterised operators
+    /// such as ParamSelection, so that a plan can be built once and evaluated
+    /// many times with different bindings.
+    ///
+    /// Panics if an operator expects parameters of a different type.
+    fn eval_with<P>(&self, params: &P) -> Vec<Output>
+    where
+        P: Any + Send + Sync,
+    {
+        self.try_eval_with_context(&EvalContext::new().with_params(params))
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Lazily evaluates the expression, producing tuples one at a time as they are pulled,
+    /// in the same order as ``eval``.
+    ///
+    /// Pipelined operations such as Selection and Projection pull tuples from their
+    /// sub-expressions on demand without buffering them, while blocking operations
+    /// such as Sort buffer only the inputs they must see in full before producing output.
+    /// The default implementation falls back on ``eval``.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Output> + 'a>
+    where
+        Output: 'a,
+    {
+        Box::new(self.eval().into_iter())
+    }
+
+    /// Lazily evaluates the expression within the limits of ``context``, producing
+    /// tuples one at a time until an error is produced in place of the next tuple.
+    ///
+    /// Pipelined operations such as Selection and Projection pull tuples from their
+    /// sub-expressions on demand. The default implementation falls back on
+    /// ``try_eval_with_context``.
+    fn try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<Output, EvalError>> + 'a>
+    where
+        Output: 'a,
+    {
+        match self.try_eval_with_context(context) {
+            Ok(rows) => Box::new(rows.into_iter().map(Ok)),
+            Err(error) => Box::new(std::iter::once(Err(error))),
+        }
+    }
+}
+
+/// Converts a value into an ``Expression``, allowing binary operations to take
+/// either an expression or an ``ExpressionBuilder`` as their right-hand side.
+pub trait IntoExpression<Output>
+where
+    Output: Clone + Eq + PartialEq,
+{
+    type Expression: Expression<Output>;
+
+    fn into_expression(self) -> Self::Expression;
+}
+
+impl<Output, E> IntoExpression<Output> for E
+where
+    Output: Clone + Eq + PartialEq,
+    E: Expression<Output>,
+{
+    type Expression = E;
+
+    fn into_expression(self) -> E {
+        self
+    }
+}
@@ -1,6 +1,11 @@
 use crate::*;
 
+use std::any::Any;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Is a utility struct for building complex ``Expression``s.
 /// 
@@ -33,9 +38,9 @@ where
         }
     }
 
-    pub fn project<T: Clone>(
+    pub fn project<T>(
         &self,
-        mapper: fn(&S) -> T,
+        mapper: impl Fn(&S) -> T + Send + Sync + 'static,
     ) -> ExpressionBuilder<T, Projection<S, T, E>>
     where
         T: Clone + Eq + PartialEq
@@ -46,29 +51,527 @@ where
         }
     }
 
-    pub fn select(&self, predicate: fn(&S) -> bool) -> ExpressionBuilder<S, Selection<S, E>> {
+    pub fn select(
+        &self,
+        predicate: impl Fn(&S) -> bool + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, Selection<S, E>> {
+        ExpressionBuilder {
+            expression: Selection::new(self.expression.clone(), predicate),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Projects each tuple onto ``columns``, either a single ``Column`` or a tuple of them.
+    pub fn project_columns<C>(
+        &self,
+        columns: C,
+    ) -> ExpressionBuilder<C::Output, Projection<S, C::Output, E>>
+    where
+        C: Columns<S>,
+    {
+        ExpressionBuilder {
+            expression: Projection::new(self.expression.clone(), move |row| columns.project(row)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Maps each tuple using a ``mapper`` that may fail, stopping evaluation at the first error.
+    pub fn try_project<T, Err>(
+        &self,
+        mapper: impl Fn(&S) -> Result<T, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<T, TryProjection<S, T, Err, E>>
+    where
+        T: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryProjection::new(self.expression.clone(), mapper),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Filters tuples using a ``predicate`` that may fail, stopping evaluation at the first error.
+    pub fn try_select<Err>(
+        &self,
+        predicate: impl Fn(&S) -> Result<bool, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, TrySelection<S, Err, E>>
+    where
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TrySelection::new(self.expression.clone(), predicate),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Maps each tuple using a ``mapper`` that receives the parameters bound by ``eval_with``.
+    pub fn param_project<T, P>(
+        &self,
+        mapper: impl Fn(&S, &P) -> T + Send + Sync + 'static,
+    ) -> ExpressionBuilder<T, ParamProjection<S, T, P, E>>
+    where
+        T: Clone + Eq + PartialEq,
+        P: Any + Send + Sync,
+    {
+        ExpressionBuilder {
+            expression: ParamProjection::new(self.expression.clone(), mapper),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Filters tuples using a ``predicate`` that receives the parameters bound by ``eval_with``.
+    pub fn param_select<P>(
+        &self,
+        predicate: impl Fn(&S, &P) -> bool + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, ParamSelection<S, P, E>>
+    where
+        P: Any + Send + Sync,
+    {
+        ExpressionBuilder {
+            expression: ParamSelection::new(self.expression.clone(), predicate),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes duplicate tuples, converting the result into a set.
+    pub fn distinct(&self) -> ExpressionBuilder<S, Distinct<S, E>>
+    where
+        S: Hash,
+    {
+        ExpressionBuilder {
+            expression: Distinct::new(self.expression.clone()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes duplicate tuples like ``distinct``, partitioning them into temporary
+    /// files once the budget of ``config`` is exceeded.
+    pub fn external_distinct(
+        &self,
+        config: SpillConfig,
+    ) -> ExpressionBuilder<S, ExternalDistinct<S, E>>
+    where
+        S: Hash + Spill,
+    {
+        ExpressionBuilder {
+            expression: ExternalDistinct::new(self.expression.clone(), config),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Groups tuples by ``key`` and reduces each group using ``aggregator``.
+    pub fn group_by<K, A>(
+        &self,
+        key: impl Fn(&S) -> K + Send + Sync + 'static,
+        aggregator: A,
+    ) -> ExpressionBuilder<(K, A::Output), GroupBy<S, K, A, E>>
+    where
+        K: Clone + Eq + PartialEq + Hash,
+        A: Aggregator<S>,
+    {
+        ExpressionBuilder {
+            expression: GroupBy::new(self.expression.clone(), key, aggregator),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Groups tuples by a ``key`` that may fail and reduces each group using ``aggregator``,
+    /// stopping evaluation at the first error.
+    pub fn try_group_by<K, A, Err>(
+        &self,
+        key: impl Fn(&S) -> Result<K, Err> + Send + Sync + 'static,
+        aggregator: A,
+    ) -> ExpressionBuilder<(K, A::Output), TryGroupBy<S, K, A, Err, E>>
+    where
+        K: Clone + Eq + PartialEq + Hash,
+        A: Aggregator<S>,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryGroupBy::new(self.expression.clone(), key, aggregator),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn join<R, Res>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, Join<S, R, Res, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: Join::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins the expression built by ``right``, which may be any expression or
+    /// another ``ExpressionBuilder``, rather than a relation.
+    pub fn join_with<R, Res, X>(
+        &self,
+        right: X,
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, Join<S, R, Res, E, X::Expression>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        X: IntoExpression<R>,
+    {
+        ExpressionBuilder {
+            expression: Join::new(
+                self.expression.clone(),
+                right.into_expression(),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation`` using a ``predicate`` and ``mapper`` that may fail,
+    /// stopping evaluation at the first error.
+    pub fn try_join<R, Res, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, TryJoin<S, R, Res, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation`` using a ``predicate`` and ``mapper`` that receive the
+    /// parameters bound by ``eval_with``.
+    pub fn param_join<R, Res, P>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R, &P) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R, &P) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, ParamJoin<S, R, Res, P, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        P: Any + Send + Sync,
+    {
+        ExpressionBuilder {
+            expression: ParamJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples of the expression that have no match.
+    pub fn left_join<R, Res>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&S, Option<&R>) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, LeftJoin<S, R, Res, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: LeftJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples of the expression that have no match,
+    /// using a ``predicate`` and ``mapper`` that may fail.
+    pub fn try_left_join<R, Res, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(&S, Option<&R>) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, TryLeftJoin<S, R, Res, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryLeftJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples of ``right_relation`` that have no match.
+    pub fn right_join<R, Res>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(Option<&S>, &R) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, RightJoin<S, R, Res, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: RightJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples of ``right_relation`` that have no match,
+    /// using a ``predicate`` and ``mapper`` that may fail.
+    pub fn try_right_join<R, Res, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(Option<&S>, &R) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, TryRightJoin<S, R, Res, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryRightJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples on either side that have no match.
+    pub fn full_join<R, Res>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(Option<&S>, Option<&R>) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, FullJoin<S, R, Res, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: FullJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation``, keeping tuples on either side that have no match,
+    /// using a ``predicate`` and ``mapper`` that may fail.
+    pub fn try_full_join<R, Res, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(Option<&S>, Option<&R>) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, TryFullJoin<S, R, Res, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryFullJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps tuples of the expression that match at least one tuple in ``right_relation``.
+    pub fn semi_join<R>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, SemiJoin<S, R, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: SemiJoin::new(self.expression.clone(), Terminal::new(right_relation), predicate),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps tuples of the expression that match at least one tuple in ``right_relation``
+    /// according to a ``predicate`` that may fail.
+    pub fn try_semi_join<R, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, TrySemiJoin<S, R, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TrySemiJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps tuples of the expression that match no tuple in ``right_relation``.
+    pub fn anti_join<R>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> bool + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, AntiJoin<S, R, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: AntiJoin::new(self.expression.clone(), Terminal::new(right_relation), predicate),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps tuples of the expression that match no tuple in ``right_relation``
+    /// according to a ``predicate`` that may fail.
+    pub fn try_anti_join<R, Err>(
+        &self,
+        right_relation: &[R],
+        predicate: impl Fn(&S, &R) -> Result<bool, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, TryAntiJoin<S, R, Err, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryAntiJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                predicate,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation`` on equal keys using a hash table rather than a nested loop.
+    pub fn equi_join<R, K, Res>(
+        &self,
+        right_relation: &[R],
+        left_key: impl Fn(&S) -> K + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> K + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, HashJoin<S, R, K, Res, E, Terminal<R>>>
+    where
+        R: Clone + Eq + PartialEq,
+        K: Clone + Eq + Hash,
+        Res: Clone + Eq + PartialEq,
+    {
         ExpressionBuilder {
-            expression: Selection::new(self.expression.clone(), predicate),
+            expression: HashJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                left_key,
+                right_key,
+                mapper,
+            ),
             phantom: PhantomData,
         }
     }
 
-    pub fn join<R, Res>(
+    /// Joins ``right_relation`` on equal keys like ``equi_join``, using key functions
+    /// and a ``mapper`` that may fail.
+    pub fn try_equi_join<R, K, Res, Err>(
         &self,
         right_relation: &[R],
-        predicate: fn(&S, &R) -> bool,
-        mapper: fn(&S, &R) -> Res,
-    ) -> ExpressionBuilder<Res, Join<S, R, Res, E, Terminal<R>>>
+        left_key: impl Fn(&S) -> Result<K, Err> + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> Result<K, Err> + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, TryHashJoin<S, R, K, Res, Err, E, Terminal<R>>>
     where
         R: Clone + Eq + PartialEq,
+        K: Eq + Hash,
         Res: Clone + Eq + PartialEq,
+        Err: Error + Send + Sync + 'static,
     {
         ExpressionBuilder {
-            expression: Join::new(
+            expression: TryHashJoin::new(
                 self.expression.clone(),
-                Terminal::new(right_relation.clone()),
-                predicate,
+                Terminal::new(right_relation),
+                left_key,
+                right_key,
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``right_relation`` on equal keys like ``equi_join``, spilling both sides
+    /// to temporary files if ``right_relation`` exceeds the budget of ``config``.
+    pub fn external_equi_join<R, K, Res>(
+        &self,
+        right_relation: &[R],
+        left_key: impl Fn(&S) -> K + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> K + Send + Sync + 'static,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
+        config: SpillConfig,
+    ) -> ExpressionBuilder<Res, ExternalHashJoin<S, R, K, Res, E, Terminal<R>>>
+    where
+        S: Spill,
+        R: Clone + Eq + PartialEq + Spill,
+        K: Clone + Eq + Hash,
+        Res: Clone + Eq + PartialEq,
+    {
+        ExpressionBuilder {
+            expression: ExternalHashJoin::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                left_key,
+                right_key,
                 mapper,
+                config,
             ),
             phantom: PhantomData,
         }
@@ -81,20 +584,197 @@ where
         }
     }
 
+    /// Combines the expression with the expression built by ``right`` using bag semantics.
+    pub fn union_with<X>(&self, right: X) -> ExpressionBuilder<S, Union<S, E, X::Expression>>
+    where
+        X: IntoExpression<S>,
+    {
+        ExpressionBuilder {
+            expression: Union::new(self.expression.clone(), right.into_expression()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Combines the expression with ``right_relation`` using bag semantics (``UNION ALL``).
+    ///
+    /// This is equivalent to ``union``.
+    pub fn union_all(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Union<S, E, Terminal<S>>> {
+        self.union(right_relation)
+    }
+
+    /// Combines the expression with ``right_relation`` using set semantics (``UNION``).
+    pub fn union_distinct(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Distinct<S, Union<S, E, Terminal<S>>>>
+    where
+        S: Hash,
+    {
+        ExpressionBuilder {
+            expression: Distinct::new(Union::new(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+            )),
+            phantom: PhantomData,
+        }
+    }
+
     pub fn intersect(
         &self,
         right_relation: &[S],
-    ) -> ExpressionBuilder<S, Intersection<S, E, Terminal<S>>> {
+    ) -> ExpressionBuilder<S, Intersection<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
         ExpressionBuilder {
             expression: Intersection::new(self.expression.clone(), Terminal::new(right_relation)),
             phantom: PhantomData,
         }
     }
 
+    /// Keeps tuples also in the expression built by ``right`` using bag semantics.
+    pub fn intersect_with<X>(
+        &self,
+        right: X,
+    ) -> ExpressionBuilder<S, Intersection<S, E, X::Expression>>
+    where
+        S: Hash,
+        X: IntoExpression<S>,
+    {
+        ExpressionBuilder {
+            expression: Intersection::new(self.expression.clone(), right.into_expression()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps tuples also in ``right_relation`` using bag semantics (``INTERSECT ALL``).
+    ///
+    /// This is equivalent to ``intersect``.
+    pub fn intersect_all(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Intersection<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
+        self.intersect(right_relation)
+    }
+
+    /// Keeps tuples also in ``right_relation`` using set semantics (``INTERSECT``).
+    pub fn intersect_distinct(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Intersection<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
+        ExpressionBuilder {
+            expression: Intersection::with_semantics(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                Semantics::Set,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes tuples in ``right_relation`` from the expression using bag semantics (``EXCEPT ALL``).
+    pub fn difference(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Difference<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
+        ExpressionBuilder {
+            expression: Difference::new(self.expression.clone(), Terminal::new(right_relation)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes tuples in ``right_relation`` from the expression using bag semantics (``EXCEPT ALL``).
+    ///
+    /// This is equivalent to ``difference``.
+    pub fn except_all(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Difference<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
+        self.difference(right_relation)
+    }
+
+    /// Removes tuples in ``right_relation`` from the expression using set semantics (``EXCEPT``).
+    pub fn except(
+        &self,
+        right_relation: &[S],
+    ) -> ExpressionBuilder<S, Difference<S, E, Terminal<S>>>
+    where
+        S: Hash,
+    {
+        ExpressionBuilder {
+            expression: Difference::with_semantics(
+                self.expression.clone(),
+                Terminal::new(right_relation),
+                Semantics::Set,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Divides the expression by ``divisor``, keeping the quotients related to every divisor tuple.
+    pub fn divide<D, Q>(
+        &self,
+        divisor: &[D],
+        quotient_mapper: impl Fn(&S) -> Q + Send + Sync + 'static,
+        divisor_mapper: impl Fn(&S) -> D + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Q, Division<S, D, Q, E, Terminal<D>>>
+    where
+        D: Clone + Eq + PartialEq + Hash,
+        Q: Clone + Eq + PartialEq + Hash,
+    {
+        ExpressionBuilder {
+            expression: Division::new(
+                self.expression.clone(),
+                Terminal::new(divisor),
+                quotient_mapper,
+                divisor_mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Divides the expression by ``divisor`` like ``divide``, using mappers that may fail.
+    pub fn try_divide<D, Q, Err>(
+        &self,
+        divisor: &[D],
+        quotient_mapper: impl Fn(&S) -> Result<Q, Err> + Send + Sync + 'static,
+        divisor_mapper: impl Fn(&S) -> Result<D, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Q, TryDivision<S, D, Q, Err, E, Terminal<D>>>
+    where
+        D: Clone + Eq + PartialEq + Hash,
+        Q: Clone + Eq + PartialEq + Hash,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TryDivision::new(
+                self.expression.clone(),
+                Terminal::new(divisor),
+                quotient_mapper,
+                divisor_mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
     pub fn cartesian_product<R, Res>(
         &self,
         right_relation: &[R],
-        mapper: fn(&S, &R) -> Res,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
     ) -> ExpressionBuilder<Res, CartesianProduct<S, R, Res, E, Terminal<R>>>
     where
         R: Clone + Eq + PartialEq,
@@ -110,7 +790,244 @@ where
         }
     }
 
+    /// Pairs every tuple with every tuple of the expression built by ``right``,
+    /// which may be any expression or another ``ExpressionBuilder``.
+    pub fn cartesian_product_with<R, Res, X>(
+        &self,
+        right: X,
+        mapper: impl Fn(&S, &R) -> Res + Send + Sync + 'static,
+    ) -> ExpressionBuilder<Res, CartesianProduct<S, R, Res, E, X::Expression>>
+    where
+        R: Clone + Eq + PartialEq,
+        Res: Clone + Eq + PartialEq,
+        X: IntoExpression<R>,
+    {
+        ExpressionBuilder {
+            expression: CartesianProduct::new(
+                self.expression.clone(),
+                right.into_expression(),
+                mapper,
+            ),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Orders tuples by ``order``, such as ``Asc``/``Desc`` key extractors or a tuple of them.
+    pub fn order_by<O>(&self, order: O) -> ExpressionBuilder<S, Sort<S, O, E>>
+    where
+        O: SortOrder<S>,
+    {
+        ExpressionBuilder {
+            expression: Sort::new(self.expression.clone(), order),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Orders tuples by ``order`` like ``order_by``, spilling sorted runs to temporary
+    /// files once the budget of ``config`` is exceeded.
+    pub fn external_order_by<O>(
+        &self,
+        order: O,
+        config: SpillConfig,
+    ) -> ExpressionBuilder<S, ExternalSort<S, O, E>>
+    where
+        S: Spill,
+        O: SortOrder<S>,
+    {
+        ExpressionBuilder {
+            expression: ExternalSort::new(self.expression.clone(), order, config),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Orders tuples using the ``comparator`` function.
+    pub fn sort_by(
+        &self,
+        comparator: impl Fn(&S, &S) -> Ordering + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, Sort<S, Arc<dyn Fn(&S, &S) -> Ordering + Send + Sync>, E>> {
+        ExpressionBuilder {
+            expression: Sort::new(self.expression.clone(), Arc::new(comparator)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Orders tuples from smallest to largest by a ``key`` that may fail, stopping
+    /// evaluation at the first error.
+    pub fn try_order_by<K, Err>(
+        &self,
+        key: impl Fn(&S) -> Result<K, Err> + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, TrySort<S, K, Err, E>>
+    where
+        K: Ord,
+        Err: Error + Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: TrySort::new(self.expression.clone(), key),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Keeps at most the first ``count`` tuples.
+    pub fn limit(&self, count: usize) -> ExpressionBuilder<S, Limit<S, E>> {
+        ExpressionBuilder {
+            expression: Limit::new(self.expression.clone(), count),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Skips the first ``count`` tuples.
+    pub fn offset(&self, count: usize) -> ExpressionBuilder<S, Offset<S, E>> {
+        ExpressionBuilder {
+            expression: Offset::new(self.expression.clone(), count),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Memoises the result of the expression so far, sharing it between clones.
+    pub fn cache(&self) -> ExpressionBuilder<S, Cached<S, E>> {
+        ExpressionBuilder {
+            expression: Cached::new(self.expression.clone()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Memoises the result of the expression so far until ``version`` changes.
+    pub fn cache_with_version(&self, version: &DataVersion) -> ExpressionBuilder<S, Cached<S, E>> {
+        ExpressionBuilder {
+            expression: Cached::with_version(self.expression.clone(), version.clone()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Erases the type of the expression so far, so that builders for plans of
+    /// different shapes have the same type.
+    pub fn boxed(&self) -> ExpressionBuilder<S, BoxedExpression<S>>
+    where
+        E: Send + Sync + 'static,
+    {
+        ExpressionBuilder {
+            expression: BoxedExpression::new(self.expression.clone()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Repeatedly applies ``step`` to newly derived tuples until no new tuples appear.
+    pub fn fixpoint<X>(
+        &self,
+        step: impl Fn(Terminal<S>, E) -> X + Send + Sync + 'static,
+    ) -> ExpressionBuilder<S, Fixpoint<S, E, X>>
+    where
+        S: Hash,
+        X: Expression<S>,
+    {
+        ExpressionBuilder {
+            expression: Fixpoint::new(self.expression.clone(), step),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the schema of the tuples produced by the expression.
+    pub fn schema(&self) -> Schema
+    where
+        S: Relation,
+    {
+        S::SCHEMA
+    }
+
     pub fn eval(&self) -> Vec<S> {
         self.expression.eval()
     }
+
+    /// Evaluates the expression once and returns a view whose result can then be
+    /// updated from changes to the expression's ``Terminal``s.
+    pub fn maintain(&self) -> MaintainedView<S, E>
+    where
+        S: Hash,
+        E: IncrementalExpression<S>,
+    {
+        MaintainedView::new(self.expression.clone())
+    }
+
+    /// Evaluates the expression with ``params`` bound for the parameterised operations,
+    /// allowing the same plan to be evaluated many times with different bindings.
+    pub fn eval_with<P>(&self, params: &P) -> Vec<S>
+    where
+        P: Any + Send + Sync,
+    {
+        self.expression.eval_with(params)
+    }
+
+    /// Evaluates the expression, returning the first error raised by a fallible operation.
+    pub fn try_eval(&self) -> Result<Vec<S>, EvalError> {
+        self.expression.try_eval()
+    }
+
+    /// Evaluates the expression within the row, time and cancellation limits of ``context``.
+    pub fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        self.expression.try_eval_with_context(context)
+    }
+
+    /// Evaluates the expression using at most ``threads`` threads.
+    ///
+    /// The result is identical to that of ``eval``.
+    pub fn par_eval(&self, threads: usize) -> Vec<S>
+    where
+        S: Send,
+        E: ParallelExpression<S>,
+    {
+        self.expression.par_eval(threads)
+    }
+
+    /// Lazily evaluates the expression, producing tuples as they are pulled.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = S> + '_> {
+        self.expression.iter()
+    }
+}
+
+impl<S, E> IntoExpression<S> for ExpressionBuilder<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    type Expression = E;
+
+    fn into_expression(self) -> E {
+        self.expression
+    }
+}
+
+impl<S, E> ExpressionBuilder<S, Cached<S, E>>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    /// Discards the memoised result, forcing the next evaluation to recompute it.
+    pub fn invalidate(&self) {
+        self.expression.invalidate()
+    }
+}
+
+impl<N, E> ExpressionBuilder<(N, N), E>
+where
+    N: Clone + Eq + PartialEq + Hash,
+    E: Expression<(N, N)>,
+{
+    /// Computes all ``(from, to)`` pairs connected by a path of one or more edges
+    /// in the edge relation described by the expression.
+    pub fn transitive_closure(
+        &self,
+    ) -> ExpressionBuilder<
+        (N, N),
+        Fixpoint<(N, N), E, HashJoin<(N, N), (N, N), N, (N, N), Terminal<(N, N)>, E>>,
+    > {
+        self.fixpoint(|delta, edges| {
+            HashJoin::new(
+                delta,
+                edges,
+                |x| x.1.clone(),
+                |y| y.0.clone(),
+                |x, y| (x.0.clone(), y.1.clone()),
+            )
+        })
+    }
 }
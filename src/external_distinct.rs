@@ -0,0 +1,278 @@
+use crate::spill::{partition, SpillDir, SpillFile};
+use crate::{EvalContext, EvalError, Expression, ParallelExpression, Spill, SpillConfig};
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::io;
+use std::marker::PhantomData;
+
+/// Removes duplicate tuples from ``expression`` without holding more than the
+/// budget of ``config`` in memory.
+///
+/// While the distinct tuples seen so far fit within the budget, this behaves like
+/// ``Distinct``. Otherwise all tuples are partitioned by hash into temporary files
+/// and duplicates are removed one partition at a time, partitioning again with a
+/// different hash seed any partition that exceeds the budget. In that case the
+/// first occurrence of each tuple is kept but tuples are ordered by partition.
+#[derive(Clone)]
+pub struct ExternalDistinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash + Spill,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub config: SpillConfig,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E> ExternalDistinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash + Spill,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, config: SpillConfig) -> Self {
+        Self {
+            expression,
+            config,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Removes duplicates from ``rows``, partitioning them once the budget is exceeded.
+    fn distinct<'a>(
+        &self,
+        mut rows: impl Iterator<Item = S>,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<S>> + 'a>>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut buffer = Vec::new();
+
+        for row in rows.by_ref() {
+            if seen.insert(row.clone()) {
+                buffer.push(row);
+
+                if buffer.len() > self.config.budget {
+                    break;
+                }
+            }
+        }
+
+        if buffer.len() <= self.config.budget {
+            return Ok(Box::new(buffer.into_iter().map(Ok)));
+        }
+
+        drop(seen);
+
+        let mut dir = SpillDir::create(&self.config)?;
+        let rows = buffer.into_iter().chain(rows).map(Ok);
+        let partitions = partition(&mut dir, rows, 0, |row| row.clone())?;
+
+        Ok(distinct_partitions(self.config.clone(), dir, partitions, 0))
+    }
+}
+
+/// Lazily removes duplicates from each partition written to ``dir`` using ``seed``.
+fn distinct_partitions<'a, S>(
+    config: SpillConfig,
+    dir: SpillDir,
+    partitions: Vec<SpillFile>,
+    seed: u64,
+) -> Box<dyn Iterator<Item = io::Result<S>> + 'a>
+where
+    S: Clone + Eq + Hash + Spill + 'a,
+{
+    Box::new(partitions.into_iter().flat_map(move |file| {
+        // Keeps the spill directory alive until every partition has been read.
+        let _dir = &dir;
+        distinct_partition(&config, &file, seed)
+    }))
+}
+
+/// Lazily removes duplicates from a single partition.
+///
+/// If the partition exceeds the budget of ``config``, it is first partitioned again
+/// with the next seed. A partition that this does not split, because its tuples are
+/// all equal, is read as it is.
+fn distinct_partition<'a, S>(
+    config: &SpillConfig,
+    file: &SpillFile,
+    seed: u64,
+) -> Box<dyn Iterator<Item = io::Result<S>> + 'a>
+where
+    S: Clone + Eq + Hash + Spill + 'a,
+{
+    if file.len() > config.budget {
+        match repartition(config, file, seed + 1) {
+            Ok(Some(rows)) => return rows,
+            Ok(None) => {}
+            Err(error) => return Box::new(std::iter::once(Err(error))),
+        }
+    }
+
+    let rows = match file.read::<S>() {
+        Ok(rows) => rows,
+        Err(error) => return Box::new(std::iter::once(Err(error))),
+    };
+    let mut seen = HashSet::new();
+
+    Box::new(rows.filter(move |row| match row {
+        Ok(row) => seen.insert(row.clone()),
+        Err(_) => true,
+    }))
+}
+
+/// Partitions ``file`` using ``seed`` and removes duplicates from the new partitions,
+/// or returns ``None`` if every tuple falls into the same partition.
+fn repartition<'a, S>(
+    config: &SpillConfig,
+    file: &SpillFile,
+    seed: u64,
+) -> io::Result<Option<Box<dyn Iterator<Item = io::Result<S>> + 'a>>>
+where
+    S: Clone + Eq + Hash + Spill + 'a,
+{
+    let mut dir = SpillDir::create(config)?;
+    let partitions = partition(&mut dir, file.read::<S>()?, seed, |row| row.clone())?;
+
+    if partitions.iter().any(|partition| partition.len() == file.len()) {
+        return Ok(None);
+    }
+
+    Ok(Some(distinct_partitions(config.clone(), dir, partitions, seed)))
+}
+
+impl<S, E> Expression<S> for ExternalDistinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash + Spill,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    /// Streams ``expression`` and checks ``context`` for each tuple pulled from it.
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut status = Ok(());
+        let input = self.expression.try_iter_with_context(context);
+        let rows = context.checked("ExternalDistinct", input, &mut status);
+        let result = self.distinct(rows).and_then(|rows| rows.collect());
+
+        status?;
+        result.map_err(|error| EvalError::new("ExternalDistinct", error))
+    }
+
+    /// Streams the distinct tuples. Panics if reading or writing a temporary file fails.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        let fail = |error| -> ! { panic!("{}", EvalError::new("ExternalDistinct", error)) };
+
+        match self.distinct(self.expression.iter()) {
+            Ok(rows) => Box::new(rows.map(move |row| row.unwrap_or_else(|error| fail(error)))),
+            Err(error) => fail(error),
+        }
+    }
+}
+
+impl<S, E> ParallelExpression<S> for ExternalDistinct<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash + Spill + Send + Sync,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Distinct, LimitExceeded, MissingParams, ParamSelection, Terminal, TrySelection};
+
+    use std::num::TryFromIntError;
+
+    #[test]
+    fn fits_in_memory() {
+        let values = &[3, 1, 3, 2, 1];
+        let config = SpillConfig::new(10);
+
+        assert_eq!(
+            ExternalDistinct::new(Terminal::new(values), config.clone()).eval(),
+            Distinct::new(Terminal::new(values)).eval()
+        );
+        assert_eq!(config.spill_count(), 0);
+    }
+
+    #[test]
+    fn spills_with_tiny_budget() {
+        let values: Vec<(i32, String)> = (0..500)
+            .map(|x| (x % 37, format!("row {}", x % 11)))
+            .collect();
+        let config = SpillConfig::new(5).with_partitions(4);
+
+        let mut result = ExternalDistinct::new(Terminal::new(&values), config.clone()).eval();
+        let mut expected = Distinct::new(Terminal::new(&values)).eval();
+        result.sort();
+        expected.sort();
+
+        assert_eq!(result, expected);
+        assert_eq!(config.spill_count(), 300);
+    }
+
+    #[test]
+    fn repartitions_large_partitions() {
+        let mut values: Vec<i32> = (0..100).chain(0..100).collect();
+        values.extend(std::iter::repeat_n(7, 50));
+        let config = SpillConfig::new(4).with_partitions(2);
+
+        let mut result = ExternalDistinct::new(Terminal::new(&values), config.clone()).eval();
+        result.sort();
+
+        assert_eq!(result, (0..100).collect::<Vec<_>>());
+        assert!(config.spill_count() > 2);
+    }
+
+    #[test]
+    fn input_rows_checked() {
+        let values: Vec<i32> = (0..1000).collect();
+        let context = EvalContext::new().with_max_rows(100);
+
+        let error = ExternalDistinct::new(Terminal::new(&values), SpillConfig::new(16))
+            .try_eval_with_context(&context)
+            .unwrap_err();
+
+        assert_eq!(error.operator, "ExternalDistinct");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(100))
+        );
+    }
+
+    #[test]
+    fn child_errors_returned() {
+        let values = &[3, 300, 3, 1];
+        let config = SpillConfig::new(1);
+
+        let error = ExternalDistinct::new(
+            TrySelection::new(Terminal::new(values), |x| {
+                Ok::<_, TryFromIntError>(u8::try_from(*x)? > 0)
+            }),
+            config.clone(),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TrySelection");
+        assert!(error.source.is::<TryFromIntError>());
+
+        let distinct = ExternalDistinct::new(
+            ParamSelection::new(Terminal::new(values), |x, min: &i32| x >= min),
+            config,
+        );
+        let mut result = distinct.eval_with(&2);
+        result.sort();
+
+        assert_eq!(result, &[3, 300]);
+        assert!(distinct.try_eval().unwrap_err().source.is::<MissingParams>());
+    }
+}
@@ -0,0 +1,441 @@
+use crate::spill::{partition, SpillDir, SpillFile};
+use crate::{EvalContext, EvalError, Expression, ParallelExpression, Spill, SpillConfig};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs whose keys are equal
+/// without holding more than the budget of ``config`` in memory.
+///
+/// A hash table is built over ``right_expression`` and probed with the tuples of
+/// ``left_expression`` as they are pulled. If ``right_expression`` exceeds the budget,
+/// both sides are instead partitioned by key into temporary files and joined one
+/// partition at a time (a grace hash join). Partitions of ``right_expression`` that
+/// exceed the budget are partitioned again with a different hash seed, or joined
+/// in budget-sized chunks if all of their tuples share a key.
+///
+/// The result contains the same tuples (as a bag) as ``HashJoin``.
+#[derive(Clone)]
+pub struct ExternalHashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Spill,
+    R: Clone + Eq + PartialEq + Spill,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub left_key: Arc<dyn Fn(&L) -> K + Send + Sync>,
+    pub right_key: Arc<dyn Fn(&R) -> K + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R) -> Res + Send + Sync>,
+    pub config: SpillConfig,
+    phantom: PhantomData<K>,
+}
+
+impl<L, R, K, Res, EL, ER> ExternalHashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Spill,
+    R: Clone + Eq + PartialEq + Spill,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        left_key: impl Fn(&L) -> K + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> K + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R) -> Res + Send + Sync + 'static,
+        config: SpillConfig,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            left_key: Arc::new(left_key),
+            right_key: Arc::new(right_key),
+            mapper: Arc::new(mapper),
+            config,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins ``left_rows`` with ``right_rows``, partitioning both once the right side
+    /// exceeds the budget.
+    fn join<'a>(
+        &'a self,
+        left_rows: impl Iterator<Item = L> + 'a,
+        mut right_rows: impl Iterator<Item = R>,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<Res>> + 'a>> {
+        let buffer: Vec<R> = right_rows.by_ref().take(self.config.budget + 1).collect();
+
+        if buffer.len() <= self.config.budget {
+            let table = self.build_table(buffer.into_iter().map(Ok))?;
+
+            return Ok(Box::new(
+                left_rows.flat_map(move |row| self.probe(&table, row).into_iter().map(Ok)),
+            ));
+        }
+
+        let mut dir = SpillDir::create(&self.config)?;
+        let right_rows = buffer.into_iter().chain(right_rows).map(Ok);
+        let right_partitions = partition(&mut dir, right_rows, 0, |row| (self.right_key)(row))?;
+        let left_rows = left_rows.map(Ok);
+        let left_partitions = partition(&mut dir, left_rows, 0, |row| (self.left_key)(row))?;
+
+        Ok(self.join_partitions(dir, right_partitions, left_partitions, 0))
+    }
+
+    /// Joins each pair of partitions written to ``dir`` using ``seed``.
+    fn join_partitions<'a>(
+        &'a self,
+        dir: SpillDir,
+        right_partitions: Vec<SpillFile>,
+        left_partitions: Vec<SpillFile>,
+        seed: u64,
+    ) -> Box<dyn Iterator<Item = io::Result<Res>> + 'a> {
+        Box::new(right_partitions.into_iter().zip(left_partitions).flat_map(
+            move |(right_file, left_file)| {
+                // Keeps the spill directory alive until every partition has been read.
+                let _dir = &dir;
+                self.join_partition(&right_file, &left_file, seed)
+            },
+        ))
+    }
+
+    /// Joins a single pair of partitions, building a hash table over ``right_file``.
+    ///
+    /// If ``right_file`` exceeds the budget, both files are partitioned again with
+    /// the next seed. If that does not split ``right_file``, because its rows all
+    /// share a key, it is instead read in chunks of at most ``budget`` rows, each
+    /// joined with the whole of ``left_file``.
+    fn join_partition<'a>(
+        &'a self,
+        right_file: &SpillFile,
+        left_file: &SpillFile,
+        seed: u64,
+    ) -> Box<dyn Iterator<Item = io::Result<Res>> + 'a> {
+        if left_file.len() == 0 {
+            return Box::new(std::iter::empty());
+        }
+
+        if right_file.len() <= self.config.budget {
+            let table = right_file.read().and_then(|rows| self.build_table(rows));
+
+            return self.probe_file(table, left_file);
+        }
+
+        match self.repartition(right_file, left_file, seed + 1) {
+            Ok(Some(rows)) => rows,
+            Ok(None) => self.join_chunks(right_file, left_file),
+            Err(error) => Box::new(std::iter::once(Err(error))),
+        }
+    }
+
+    /// Partitions both files using ``seed`` and joins the new partitions, or returns
+    /// ``None`` if every row of ``right_file`` falls into the same partition.
+    fn repartition<'a>(
+        &'a self,
+        right_file: &SpillFile,
+        left_file: &SpillFile,
+        seed: u64,
+    ) -> io::Result<Option<Box<dyn Iterator<Item = io::Result<Res>> + 'a>>> {
+        let mut dir = SpillDir::create(&self.config)?;
+        let right_partitions = partition(&mut dir, right_file.read()?, seed, |row| {
+            (self.right_key)(row)
+        })?;
+
+        if right_partitions
+            .iter()
+            .any(|file| file.len() == right_file.len())
+        {
+            return Ok(None);
+        }
+
+        let left_partitions = partition(&mut dir, left_file.read()?, seed, |row| {
+            (self.left_key)(row)
+        })?;
+
+        Ok(Some(self.join_partitions(
+            dir,
+            right_partitions,
+            left_partitions,
+            seed,
+        )))
+    }
+
+    /// Joins ``right_file`` in chunks of at most ``budget`` rows, reading ``left_file``
+    /// once for each chunk.
+    fn join_chunks<'a>(
+        &'a self,
+        right_file: &SpillFile,
+        left_file: &SpillFile,
+    ) -> Box<dyn Iterator<Item = io::Result<Res>> + 'a> {
+        let mut right_rows = match right_file.read::<R>() {
+            Ok(rows) => rows,
+            Err(error) => return Box::new(std::iter::once(Err(error))),
+        };
+        let left_file = left_file.clone();
+        let budget = self.config.budget;
+
+        let chunks = std::iter::from_fn(move || {
+            let chunk: Vec<_> = right_rows.by_ref().take(budget).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        });
+
+        Box::new(chunks.flat_map(move |chunk| {
+            self.probe_file(self.build_table(chunk.into_iter()), &left_file)
+        }))
+    }
+
+    /// Probes ``table`` with each row of ``left_file``.
+    fn probe_file<'a>(
+        &'a self,
+        table: io::Result<HashMap<K, Vec<R>>>,
+        left_file: &SpillFile,
+    ) -> Box<dyn Iterator<Item = io::Result<Res>> + 'a> {
+        match table.and_then(|table| Ok((table, left_file.read::<L>()?))) {
+            Ok((table, left_rows)) => Box::new(left_rows.flat_map(move |row| match row {
+                Ok(row) => self.probe(&table, row).into_iter().map(Ok).collect(),
+                Err(error) => vec![Err(error)],
+            })),
+            Err(error) => Box::new(std::iter::once(Err(error))),
+        }
+    }
+
+    fn build_table(
+        &self,
+        rows: impl Iterator<Item = io::Result<R>>,
+    ) -> io::Result<HashMap<K, Vec<R>>> {
+        let mut table: HashMap<K, Vec<R>> = HashMap::new();
+
+        for row in rows {
+            let row = row?;
+            table.entry((self.right_key)(&row)).or_default().push(row);
+        }
+
+        Ok(table)
+    }
+
+    fn probe(&self, table: &HashMap<K, Vec<R>>, row: L) -> Vec<Res> {
+        match table.get(&(self.left_key)(&row)) {
+            Some(rows) => rows
+                .iter()
+                .map(|other| (self.mapper)(&row, other))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<L, R, K, Res, EL, ER> Expression<Res> for ExternalHashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Spill,
+    R: Clone + Eq + PartialEq + Spill,
+    K: Clone + Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.iter().collect()
+    }
+
+    /// Streams both sides and checks ``context`` for each tuple pulled from either.
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        let operator = "ExternalHashJoin";
+        let (mut left_status, mut right_status) = (Ok(()), Ok(()));
+        let left_input = self.left_expression.try_iter_with_context(context);
+        let right_input = self.right_expression.try_iter_with_context(context);
+        let left_rows = context.checked(operator, left_input, &mut left_status);
+        let right_rows = context.checked(operator, right_input, &mut right_status);
+        let result = self
+            .join(left_rows, right_rows)
+            .and_then(|rows| rows.collect());
+
+        right_status?;
+        left_status?;
+        result.map_err(|error| EvalError::new(operator, error))
+    }
+
+    /// Streams the joined tuples. Panics if reading or writing a temporary file fails.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Res> + 'a>
+    where
+        Res: 'a,
+    {
+        let fail = |error| -> ! { panic!("{}", EvalError::new("ExternalHashJoin", error)) };
+
+        match self.join(self.left_expression.iter(), self.right_expression.iter()) {
+            Ok(rows) => Box::new(rows.map(move |row| row.unwrap_or_else(|error| fail(error)))),
+            Err(error) => fail(error),
+        }
+    }
+}
+
+impl<L, R, K, Res, EL, ER> ParallelExpression<Res> for ExternalHashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Spill + Send + Sync,
+    R: Clone + Eq + PartialEq + Spill + Send + Sync,
+    K: Clone + Eq + Hash + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HashJoin, LimitExceeded, MissingParams, ParamSelection, Terminal, TrySelection};
+
+    use std::num::TryFromIntError;
+
+    #[test]
+    fn fits_in_memory() {
+        let values1 = &[(1, 'a'), (2, 'b'), (2, 'c')];
+        let values2 = &[(2, 'x'), (3, 'y')];
+        let config = SpillConfig::new(10);
+
+        assert_eq!(
+            ExternalHashJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x| x.0,
+                |y| y.0,
+                |x, y| (x.1, y.1),
+                config.clone(),
+            )
+            .eval(),
+            &[('b', 'x'), ('c', 'x')]
+        );
+        assert_eq!(config.spill_count(), 0);
+    }
+
+    #[test]
+    fn spills_with_tiny_budget() {
+        let values1: Vec<(i32, String)> =
+            (0..300).map(|x| (x % 50, format!("left {}", x))).collect();
+        let values2: Vec<(i32, String)> =
+            (0..200).map(|x| (x % 70, format!("right {}", x))).collect();
+        let config = SpillConfig::new(8).with_partitions(8);
+
+        let mut result = ExternalHashJoin::new(
+            Terminal::new(&values1),
+            Terminal::new(&values2),
+            |x| x.0,
+            |y| y.0,
+            |x, y| (x.1.clone(), y.1.clone()),
+            config.clone(),
+        )
+        .eval();
+        let mut expected = HashJoin::new(
+            Terminal::new(&values1),
+            Terminal::new(&values2),
+            |x: &(i32, String)| x.0,
+            |y: &(i32, String)| y.0,
+            |x, y| (x.1.clone(), y.1.clone()),
+        )
+        .eval();
+        result.sort();
+        expected.sort();
+
+        assert_eq!(result, expected);
+        assert_eq!(config.spill_count(), 208);
+    }
+
+    #[test]
+    fn repartitions_large_partitions() {
+        let values1: Vec<(i32, i32)> = (0..100).map(|x| (x % 40, x)).collect();
+        let mut values2: Vec<(i32, i32)> = (0..100).map(|x| (x, x)).collect();
+        values2.extend((0..30).map(|x| (7, x)));
+        let config = SpillConfig::new(4).with_partitions(2);
+
+        let mut result = ExternalHashJoin::new(
+            Terminal::new(&values1),
+            Terminal::new(&values2),
+            |x| x.0,
+            |y| y.0,
+            |x, y| (x.1, y.1),
+            config.clone(),
+        )
+        .eval();
+        let mut expected = HashJoin::new(
+            Terminal::new(&values1),
+            Terminal::new(&values2),
+            |x: &(i32, i32)| x.0,
+            |y: &(i32, i32)| y.0,
+            |x, y| (x.1, y.1),
+        )
+        .eval();
+        result.sort();
+        expected.sort();
+
+        assert_eq!(result.len(), 100 + 3 * 30);
+        assert_eq!(result, expected);
+        assert!(config.spill_count() > 4);
+    }
+
+    #[test]
+    fn input_rows_checked() {
+        let values: Vec<i32> = (0..1000).collect();
+        let context = EvalContext::new().with_max_rows(100);
+
+        let error = ExternalHashJoin::new(
+            Terminal::new(&values),
+            Terminal::new(&[1, 2]),
+            |x| *x,
+            |y| *y,
+            |x, y| (*x, *y),
+            SpillConfig::new(16),
+        )
+        .try_eval_with_context(&context)
+        .unwrap_err();
+
+        assert_eq!(error.operator, "ExternalHashJoin");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(100))
+        );
+    }
+
+    #[test]
+    fn child_errors_returned() {
+        let values = &[3, 300, 1];
+        let config = SpillConfig::new(1);
+
+        let error = ExternalHashJoin::new(
+            Terminal::new(values),
+            TrySelection::new(Terminal::new(values), |x| {
+                Ok::<_, TryFromIntError>(u8::try_from(*x)? > 0)
+            }),
+            |x| *x,
+            |y| *y,
+            |x, y| (*x, *y),
+            config.clone(),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TrySelection");
+        assert!(error.source.is::<TryFromIntError>());
+
+        let join = ExternalHashJoin::new(
+            ParamSelection::new(Terminal::new(values), |x, min: &i32| x >= min),
+            Terminal::new(values),
+            |x| *x,
+            |y| *y,
+            |x, y| (*x, *y),
+            config,
+        );
+        let mut result = join.eval_with(&2);
+        result.sort();
+
+        assert_eq!(result, &[(3, 3), (300, 300)]);
+        assert!(join.try_eval().unwrap_err().source.is::<MissingParams>());
+    }
+}
@@ -0,0 +1,330 @@
+use crate::spill::{SpillDir, SpillFile};
+use crate::{
+    EvalContext, EvalError, Expression, ParallelExpression, SortOrder, Spill, SpillConfig,
+};
+
+use std::cmp::Ordering;
+use std::io;
+use std::marker::PhantomData;
+
+/// The maximum number of runs merged at once, bounding the number of open files.
+const MAX_MERGE_WIDTH: usize = 64;
+
+/// Orders the tuples in ``expression`` according to ``order`` without holding
+/// more than the budget of ``config`` in memory.
+///
+/// Tuples are pulled from ``expression`` and sorted in runs of at most ``budget``
+/// tuples, which are written to temporary files and merged as the result is pulled.
+/// If the input fits within the budget, it is sorted in memory as by ``Sort``.
+/// Like ``Sort``, the sort is stable.
+#[derive(Clone)]
+pub struct ExternalSort<S, O, E>
+where
+    S: Clone + Eq + PartialEq + Spill,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub order: O,
+    pub config: SpillConfig,
+    phantom: PhantomData<S>,
+}
+
+impl<S, O, E> ExternalSort<S, O, E>
+where
+    S: Clone + Eq + PartialEq + Spill,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, order: O, config: SpillConfig) -> Self {
+        Self {
+            expression,
+            order,
+            config,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sorts ``rows``, spilling sorted runs whenever the budget is reached.
+    fn sort<'a>(
+        &self,
+        rows: impl Iterator<Item = S>,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<S>> + 'a>>
+    where
+        S: 'a,
+        O: 'a,
+    {
+        let mut dir = None;
+        let mut runs = Vec::new();
+        let mut buffer = Vec::new();
+
+        for row in rows {
+            buffer.push(row);
+
+            if buffer.len() >= self.config.budget {
+                let dir = match &mut dir {
+                    Some(dir) => dir,
+                    None => dir.insert(SpillDir::create(&self.config)?),
+                };
+
+                buffer.sort_by(|a, b| self.order.compare(a, b));
+                runs.push(dir.write(buffer.drain(..))?);
+            }
+        }
+
+        buffer.sort_by(|a, b| self.order.compare(a, b));
+
+        let Some(mut dir) = dir else {
+            return Ok(Box::new(buffer.into_iter().map(Ok)));
+        };
+
+        while runs.len() > MAX_MERGE_WIDTH {
+            runs = runs
+                .chunks(MAX_MERGE_WIDTH)
+                .map(|batch| {
+                    let mut file = dir.create_file()?;
+                    for row in Merge::new(read_runs(batch)?, self.order.clone())? {
+                        file.push(&row?)?;
+                    }
+                    file.finish()
+                })
+                .collect::<io::Result<_>>()?;
+        }
+
+        let mut sources = read_runs(&runs)?;
+        sources.push(Box::new(buffer.into_iter().map(Ok)));
+
+        let mut merge = Merge::new(sources, self.order.clone())?;
+        merge.dir = Some(dir);
+
+        Ok(Box::new(merge))
+    }
+}
+
+/// Opens each of ``runs`` for reading.
+fn read_runs<'a, S>(
+    runs: &[SpillFile],
+) -> io::Result<Vec<Box<dyn Iterator<Item = io::Result<S>> + 'a>>>
+where
+    S: Spill + 'a,
+{
+    runs.iter()
+        .map(|run| {
+            run.read()
+                .map(|rows| Box::new(rows) as Box<dyn Iterator<Item = io::Result<S>>>)
+        })
+        .collect()
+}
+
+/// Merges sorted ``sources`` into a single sorted sequence, preferring earlier
+/// sources on ties so that the merge is stable.
+struct Merge<'a, S, O> {
+    sources: Vec<Box<dyn Iterator<Item = io::Result<S>> + 'a>>,
+    heads: Vec<Option<S>>,
+    order: O,
+    dir: Option<SpillDir>,
+}
+
+impl<'a, S, O> Merge<'a, S, O>
+where
+    O: SortOrder<S>,
+{
+    fn new(
+        mut sources: Vec<Box<dyn Iterator<Item = io::Result<S>> + 'a>>,
+        order: O,
+    ) -> io::Result<Self> {
+        let heads = sources
+            .iter_mut()
+            .map(|source| source.next().transpose())
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            sources,
+            heads,
+            order,
+            dir: None,
+        })
+    }
+}
+
+impl<'a, S, O> Iterator for Merge<'a, S, O>
+where
+    O: SortOrder<S>,
+{
+    type Item = io::Result<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut smallest: Option<(usize, &S)> = None;
+
+        for (index, head) in self.heads.iter().enumerate() {
+            if let Some(row) = head {
+                match smallest {
+                    Some((_, other)) if self.order.compare(row, other) != Ordering::Less => {}
+                    _ => smallest = Some((index, row)),
+                }
+            }
+        }
+
+        let (index, _) = smallest?;
+        let row = self.heads[index].take()?;
+
+        match self.sources[index].next().transpose() {
+            Ok(head) => self.heads[index] = head,
+            Err(error) => return Some(Err(error)),
+        }
+
+        Some(Ok(row))
+    }
+}
+
+impl<S, O, E> Expression<S> for ExternalSort<S, O, E>
+where
+    S: Clone + Eq + PartialEq + Spill,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    /// Streams ``expression`` and checks ``context`` for each tuple pulled from it.
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut status = Ok(());
+        let input = self.expression.try_iter_with_context(context);
+        let rows = context.checked("ExternalSort", input, &mut status);
+        let result = self.sort(rows).and_then(|rows| rows.collect());
+
+        status?;
+        result.map_err(|error| EvalError::new("ExternalSort", error))
+    }
+
+    /// Streams the merged runs. Panics if reading or writing a temporary file fails.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        let fail = |error| -> ! { panic!("{}", EvalError::new("ExternalSort", error)) };
+
+        match self.sort(self.expression.iter()) {
+            Ok(rows) => Box::new(rows.map(move |row| row.unwrap_or_else(|error| fail(error)))),
+            Err(error) => fail(error),
+        }
+    }
+}
+
+impl<S, O, E> ParallelExpression<S> for ExternalSort<S, O, E>
+where
+    S: Clone + Eq + PartialEq + Spill + Send + Sync,
+    O: SortOrder<S> + Sync,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        Asc, Desc, Generator, LimitExceeded, MissingParams, ParamSelection, Sort, Terminal,
+        TrySelection,
+    };
+
+    use std::num::TryFromIntError;
+
+    #[test]
+    fn fits_in_memory() {
+        let values = &[3, 1, 2];
+        let config = SpillConfig::new(10);
+
+        assert_eq!(
+            ExternalSort::new(
+                Terminal::new(values),
+                Asc::new(|x: &i32| *x),
+                config.clone()
+            )
+            .eval(),
+            &[1, 2, 3]
+        );
+        assert_eq!(config.spill_count(), 0);
+    }
+
+    #[test]
+    fn spills_with_tiny_budget() {
+        let values: Vec<(i32, String)> = (0..500)
+            .map(|x| ((x * 7919) % 100, format!("row {}", x)))
+            .collect();
+        let order = Desc::new(|x: &(i32, String)| x.0);
+        let config = SpillConfig::new(3);
+
+        let external = ExternalSort::new(Terminal::new(&values), order.clone(), config.clone());
+
+        assert_eq!(
+            external.eval(),
+            Sort::new(Terminal::new(&values), order).eval()
+        );
+        assert!(config.spill_count() > MAX_MERGE_WIDTH);
+    }
+
+    #[test]
+    fn input_rows_checked() {
+        let values: Vec<i32> = (0..1000).collect();
+        let context = EvalContext::new().with_max_rows(100);
+
+        let order = Asc::new(|x: &i32| *x);
+
+        let error = ExternalSort::new(Terminal::new(&values), order, SpillConfig::new(16))
+            .try_eval_with_context(&context)
+            .unwrap_err();
+
+        assert_eq!(error.operator, "ExternalSort");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(100))
+        );
+    }
+
+    #[test]
+    fn child_errors_returned() {
+        let values = &[3, 300, 1];
+        let order = Asc::new(|x: &i32| *x);
+        let config = SpillConfig::new(2);
+
+        let error = ExternalSort::new(
+            TrySelection::new(Terminal::new(values), |x| {
+                Ok::<_, TryFromIntError>(u8::try_from(*x)? > 0)
+            }),
+            order.clone(),
+            config.clone(),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TrySelection");
+        assert!(error.source.is::<TryFromIntError>());
+
+        let sort = ExternalSort::new(
+            ParamSelection::new(Terminal::new(values), |x, min: &i32| x >= min),
+            order,
+            config,
+        );
+
+        assert_eq!(sort.eval_with(&2), &[3, 300]);
+        assert!(sort.try_eval().unwrap_err().source.is::<MissingParams>());
+    }
+
+    #[test]
+    fn unbounded_input_stopped() {
+        let context = EvalContext::new().with_max_rows(100);
+
+        let order = Asc::new(|x: &u64| *x);
+
+        let error = ExternalSort::new(Generator::range(0..), order, SpillConfig::new(16))
+            .try_eval_with_context(&context)
+            .unwrap_err();
+
+        assert_eq!(error.operator, "Generator");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(100))
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression, Terminal};
+
+use std::convert::Infallible;
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Computes the least fixpoint of ``R = base_expression ∪ step(R)``.
+///
+/// This allows recursive queries such as reachability in a graph, which cannot
+/// be expressed with a fixed number of joins. ``step`` is given a relation of
+/// tuples together with a clone of ``base_expression`` and builds an expression
+/// (typically a ``Join`` or ``HashJoin``) deriving new tuples from them.
+///
+/// Evaluation is semi-naive: each iteration only passes the tuples derived in the
+/// previous iteration (the delta) to ``step``, stopping once no new tuples appear.
+/// This is correct as long as ``step`` is linear in its first argument, which holds
+/// for steps built from selections, projections and joins against fixed relations.
+/// The result uses set semantics.
+#[derive(Clone)]
+pub struct Fixpoint<S, E, X>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+    X: Expression<S>,
+{
+    pub base_expression: E,
+    pub step: Arc<dyn Fn(Terminal<S>, E) -> X + Send + Sync>,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E, X> Fixpoint<S, E, X>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+    X: Expression<S>,
+{
+    pub fn new(
+        base_expression: E,
+        step: impl Fn(Terminal<S>, E) -> X + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            base_expression,
+            step: Arc::new(step),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, E, X> Expression<S> for Fixpoint<S, E, X>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+    X: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        let Ok(result) = self.iterate(self.base_expression.eval(), |step| {
+            Ok::<_, Infallible>(step.eval())
+        });
+        result
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        self.iterate(
+            self.base_expression.try_eval_with_context(context)?,
+            |step| step.try_eval_with_context(context),
+        )
+    }
+}
+
+impl<S, E, X> ParallelExpression<S> for Fixpoint<S, E, X>
+where
+    S: Clone + Eq + PartialEq + Hash + Send + Sync,
+    E: ParallelExpression<S>,
+    X: ParallelExpression<S>,
+{
+    /// Evaluates the base expression and each step in parallel, although the
+    /// iterations themselves are inherently sequential.
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let Ok(result) = self.iterate(self.base_expression.par_eval(threads), |step| {
+            Ok::<_, Infallible>(step.par_eval(threads))
+        });
+        result
+    }
+}
+
+impl<S, E, X> Fixpoint<S, E, X>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: Expression<S>,
+    X: Expression<S>,
+{
+    /// Applies ``step`` to each delta starting from ``base_result`` until no new tuples appear,
+    /// evaluating the step expressions with ``eval_step`` and stopping at the first error.
+    fn iterate<Err>(
+        &self,
+        base_result: Vec<S>,
+        eval_step: impl Fn(X) -> Result<Vec<S>, Err>,
+    ) -> Result<Vec<S>, Err> {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+
+        let mut delta: Vec<S> = base_result
+            .into_iter()
+            .filter(|row| seen.insert(row.clone()))
+            .collect();
+
+        while !delta.is_empty() {
+            let derived = eval_step((self.step)(
+                Terminal::new(&delta),
+                self.base_expression.clone(),
+            ))?;
+            result.append(&mut delta);
+
+            delta = derived
+                .into_iter()
+                .filter(|row| seen.insert(row.clone()))
+                .collect();
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HashJoin, Join, Projection, Selection};
+
+    #[test]
+    fn transitive_closure_of_chain() {
+        let edges = &[(1, 2), (2, 3), (3, 4)];
+
+        let mut result = Fixpoint::new(Terminal::new(edges), |delta, edges| {
+            Join::new(delta, edges, |x, y| x.1 == y.0, |x, y| (x.0, y.1))
+        })
+        .eval();
+        result.sort();
+
+        assert_eq!(result, &[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+    }
+
+    #[test]
+    fn transitive_closure_of_cycle_terminates() {
+        let edges = &[(1, 2), (2, 1), (2, 2)];
+
+        let mut result = Fixpoint::new(Terminal::new(edges), |delta, edges| {
+            HashJoin::new(delta, edges, |x| x.1, |y| y.0, |x, y| (x.0, y.1))
+        })
+        .eval();
+        result.sort();
+
+        assert_eq!(result, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn base_duplicates_removed() {
+        let values = &[1, 1, 2];
+
+        assert_eq!(
+            Fixpoint::new(Terminal::new(values), |_, _| Terminal::new(&[])).eval(),
+            &[1, 2]
+        );
+    }
+
+    #[test]
+    fn counting_to_bound() {
+        let values = &[0];
+
+        assert_eq!(
+            Fixpoint::new(Terminal::new(values), |delta, _| {
+                Projection::new(Selection::new(delta, |x| *x < 5), |x| x + 1)
+            })
+            .eval(),
+            &[0, 1, 2, 3, 4, 5]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::parallel::par_join;
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match some condition ``predicate``,
+/// as well as every tuple on either side that matches no tuple on the other.
+///
+/// Unmatched tuples are passed to the mapper with ``None`` in place of the missing side.
+/// Matched pairs and unmatched left tuples appear in left order, followed by the
+/// unmatched right tuples in right order.
+#[derive(Clone)]
+pub struct FullJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> bool + Send + Sync>,
+    pub mapper: Arc<dyn Fn(Option<&L>, Option<&R>) -> Res + Send + Sync>,
+}
+
+impl<L, R, Res, EL, ER> FullJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(Option<&L>, Option<&R>) -> Res + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins the evaluated results of both sides.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Vec<Res> {
+        let mut result = Vec::new();
+        let mut right_matched = vec![false; right_result.len()];
+
+        for row1 in left_result {
+            let mut matched = false;
+
+            for (row2, row2_matched) in right_result.iter().zip(right_matched.iter_mut()) {
+                if (self.predicate)(row1, row2) {
+                    matched = true;
+                    *row2_matched = true;
+                    result.push((self.mapper)(Some(row1), Some(row2)));
+                }
+            }
+
+            if !matched {
+                result.push((self.mapper)(Some(row1), None));
+            }
+        }
+
+        for (row2, row2_matched) in right_result.iter().zip(right_matched) {
+            if !row2_matched {
+                result.push((self.mapper)(None, Some(row2)));
+            }
+        }
+
+        result
+    }
+}
+
+impl<L, R, Res, EL, ER> Expression<Res> for FullJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.combine(&self.left_expression.eval(), &self.right_expression.eval())
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        Ok(self.combine(
+            &self.left_expression.try_eval_with_context(context)?,
+            &self.right_expression.try_eval_with_context(context)?,
+        ))
+    }
+}
+
+impl<L, R, Res, EL, ER> ParallelExpression<Res> for FullJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently.
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        self.combine(&left_result, &right_result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn all_rows_matched() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            FullJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+            )
+            .eval(),
+            &[(Some("a"), Some("x")), (Some("b"), Some("y"))]
+        );
+    }
+
+    #[test]
+    fn unmatched_rows_on_both_sides() {
+        let values1 = &[(1, "a"), (2, "b"), (3, "c")];
+        let values2 = &[(2, "y"), (4, "z")];
+
+        assert_eq!(
+            FullJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+            )
+            .eval(),
+            &[
+                (Some("a"), None),
+                (Some("b"), Some("y")),
+                (Some("c"), None),
+                (None, Some("z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_matches() {
+        let values1 = &[(1, "a"), (1, "b")];
+        let values2 = &[(1, "x"), (1, "y"), (2, "z")];
+
+        assert_eq!(
+            FullJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+            )
+            .eval(),
+            &[
+                (Some("a"), Some("x")),
+                (Some("a"), Some("y")),
+                (Some("b"), Some("x")),
+                (Some("b"), Some("y")),
+                (None, Some("z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_sides() {
+        let values = &[(1, "a")];
+        let empty: &[(i32, &str)] = &[];
+
+        assert_eq!(
+            FullJoin::new(
+                Terminal::new(values),
+                Terminal::new(empty),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+            )
+            .eval(),
+            &[(Some("a"), None)]
+        );
+        assert_eq!(
+            FullJoin::new(
+                Terminal::new(empty),
+                Terminal::new(values),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+            )
+            .eval(),
+            &[(None, Some("a"))]
+        );
+    }
+}
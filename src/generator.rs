@@ -0,0 +1,119 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::sync::Arc;
+
+/// A relation whose rows are produced on demand by ``generator`` each time it
+/// is evaluated, rather than stored up front like those of a ``Terminal``.
+///
+/// Since rows are only produced as they are pulled, an unbounded generator can be
+/// iterated with ``iter``, or evaluated with ``try_eval_with_context`` under a row
+/// limit, deadline or cancellation token.
+#[derive(Clone)]
+pub struct Generator<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    pub generator: Arc<dyn Fn() -> Box<dyn Iterator<Item = S>> + Send + Sync>,
+}
+
+impl<S> Generator<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    pub fn new<I>(generator: impl Fn() -> I + Send + Sync + 'static) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        I::IntoIter: 'static,
+    {
+        Self {
+            generator: Arc::new(move || Box::new(generator().into_iter())),
+        }
+    }
+
+    /// Produces the rows of ``range``, or any other iterator that can be cloned,
+    /// afresh on each evaluation.
+    pub fn range<I>(range: I) -> Self
+    where
+        I: Iterator<Item = S> + Clone + Send + Sync + 'static,
+    {
+        Self::new(move || range.clone())
+    }
+}
+
+impl<S> Expression<S> for Generator<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn eval(&self) -> Vec<S> {
+        (self.generator)().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut result = Vec::new();
+
+        for row in (self.generator)() {
+            result.push(row);
+            context.check("Generator", result.len())?;
+        }
+
+        Ok(result)
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        (self.generator)()
+    }
+
+    /// Checks ``context`` for each row as it is produced.
+    fn try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<S, EvalError>> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new((self.generator)().enumerate().map(move |(index, row)| {
+            context.check("Generator", index + 1)?;
+            Ok(row)
+        }))
+    }
+}
+
+impl<S> ParallelExpression<S> for Generator<S>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Limit, LimitExceeded};
+
+    #[test]
+    fn numeric_range() {
+        let range = Generator::range(1..=5);
+
+        assert_eq!(range.eval(), &[1, 2, 3, 4, 5]);
+        assert_eq!(range.eval(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn rows_produced_on_demand() {
+        let squares = Generator::range((0..).map(|x: u64| x * x));
+
+        assert_eq!(Limit::new(squares.clone(), 4).iter().collect::<Vec<_>>(), &[0, 1, 4, 9]);
+
+        let error = squares
+            .try_eval_with_context(&EvalContext::new().with_max_rows(100))
+            .unwrap_err();
+
+        assert_eq!(error.operator, "Generator");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(100))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{Aggregator, EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Partitions the tuples in ``expression`` into groups sharing the same ``key``
+/// and reduces each group to a single tuple using ``aggregator``.
+///
+/// Each resulting tuple consists of the group key followed by the aggregated value.
+/// Groups appear in the order their keys are first encountered.
+#[derive(Clone)]
+pub struct GroupBy<S, K, A, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub key: Arc<dyn Fn(&S) -> K + Send + Sync>,
+    pub aggregator: A,
+}
+
+impl<S, K, A, E> GroupBy<S, K, A, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    E: Expression<S>,
+{
+    pub fn new(
+        expression: E,
+        key: impl Fn(&S) -> K + Send + Sync + 'static,
+        aggregator: A,
+    ) -> Self {
+        Self {
+            expression,
+            key: Arc::new(key),
+            aggregator,
+        }
+    }
+}
+
+impl<S, K, A, E> Expression<(K, A::Output)> for GroupBy<S, K, A, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<(K, A::Output)> {
+        self.aggregate(&self.expression.eval())
+    }
+
+    fn try_eval_with_context(
+        &self,
+        context: &EvalContext,
+    ) -> Result<Vec<(K, A::Output)>, EvalError> {
+        Ok(self.aggregate(&self.expression.try_eval_with_context(context)?))
+    }
+}
+
+impl<S, K, A, E> ParallelExpression<(K, A::Output)> for GroupBy<S, K, A, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    K: Clone + Eq + PartialEq + Hash + Send + Sync,
+    A: Aggregator<S> + Sync,
+    A::Output: Send,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<(K, A::Output)> {
+        self.aggregate(&self.expression.par_eval(threads))
+    }
+}
+
+impl<S, K, A, E> GroupBy<S, K, A, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    E: Expression<S>,
+{
+    /// Groups and aggregates the evaluated result of ``expression``.
+    fn aggregate(&self, rows: &[S]) -> Vec<(K, A::Output)> {
+        let mut indices: HashMap<K, usize> = HashMap::new();
+        let mut groups: Vec<(K, A::State)> = Vec::new();
+
+        for row in rows {
+            let key = (self.key)(row);
+            let index = *indices.entry(key.clone()).or_insert_with(|| {
+                groups.push((key, self.aggregator.init()));
+                groups.len() - 1
+            });
+
+            self.aggregator.accumulate(&mut groups[index].1, row);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, state)| (key, self.aggregator.finish(state)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Average, Avg, Count, Max, Min, Sum, Terminal};
+
+    #[test]
+    fn count_groups() {
+        let values = &[("a", 1), ("b", 2), ("a", 3)];
+
+        assert_eq!(
+            GroupBy::new(Terminal::new(values), |x| x.0, Count).eval(),
+            &[("a", 2), ("b", 1)]
+        );
+    }
+
+    #[test]
+    fn multiple_aggregates() {
+        let values = &[("a", 1), ("b", 2), ("a", 3), ("b", 8), ("b", 5)];
+
+        let expected_result = &[
+            ("a", (2, 4, 1, 3, Average(2.0))),
+            ("b", (3, 15, 2, 8, Average(5.0))),
+        ];
+
+        assert_eq!(
+            GroupBy::new(
+                Terminal::new(values),
+                |x| x.0,
+                (
+                    Count,
+                    Sum::new(|x: &(&str, i32)| x.1),
+                    Min::new(|x: &(&str, i32)| x.1),
+                    Max::new(|x: &(&str, i32)| x.1),
+                    Avg::new(|x: &(&str, i32)| x.1),
+                ),
+            )
+            .eval(),
+            expected_result
+        );
+    }
+
+    #[test]
+    fn average_wide_integers() {
+        let values: &[(&str, i64)] = &[("a", 4_000_000_000), ("a", 6_000_000_000), ("b", -3)];
+
+        assert_eq!(
+            GroupBy::new(Terminal::new(values), |x| x.0, Avg::new(|x: &(&str, i64)| x.1)).eval(),
+            &[("a", Average(5_000_000_000.0)), ("b", Average(-3.0))]
+        );
+    }
+
+    #[test]
+    fn empty_relation() {
+        let values: &[(&str, i32)] = &[];
+
+        assert_eq!(GroupBy::new(Terminal::new(values), |x| x.0, Count).eval(), &[]);
+    }
+
+    #[test]
+    fn user_defined_aggregator() {
+        #[derive(Clone)]
+        struct Concat;
+
+        impl Aggregator<(i32, &'static str)> for Concat {
+            type State = String;
+            type Output = String;
+
+            fn init(&self) -> String {
+                String::new()
+            }
+
+            fn accumulate(&self, state: &mut String, row: &(i32, &'static str)) {
+                state.push_str(row.1);
+            }
+
+            fn finish(&self, state: String) -> String {
+                state
+            }
+        }
+
+        let values = &[(1, "a"), (2, "b"), (1, "c")];
+
+        assert_eq!(
+            GroupBy::new(Terminal::new(values), |x| x.0, Concat).eval(),
+            &[(1, String::from("ac")), (2, String::from("b"))]
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use crate::parallel::{par_chunks, par_join};
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs whose keys are equal.
+///
+/// This is the equi-join counterpart of ``Join``. Rather than testing every pair
+/// of tuples against a predicate, a hash table is built over the smaller of the two
+/// inputs and probed with the tuples of the other, giving linear rather than
+/// quadratic running time.
+///
+/// The result contains exactly the same tuples (as a bag) as a ``Join`` with
+/// predicate ``left_key(x) == right_key(y)``, although not necessarily in the same order.
+#[derive(Clone)]
+pub struct HashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub left_key: Arc<dyn Fn(&L) -> K + Send + Sync>,
+    pub right_key: Arc<dyn Fn(&R) -> K + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R) -> Res + Send + Sync>,
+    phantom: PhantomData<K>,
+}
+
+impl<L, R, K, Res, EL, ER> HashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        left_key: impl Fn(&L) -> K + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> K + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R) -> Res + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            left_key: Arc::new(left_key),
+            right_key: Arc::new(right_key),
+            mapper: Arc::new(mapper),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Joins the evaluated results of both sides, building the hash table over the smaller one.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Vec<Res> {
+        if left_result.len() < right_result.len() {
+            self.probe_right(&build_table(left_result, &*self.left_key), right_result)
+        } else {
+            self.probe_left(&build_table(right_result, &*self.right_key), left_result)
+        }
+    }
+
+    /// Looks up each of ``rows`` in a ``table`` built over the right side.
+    fn probe_left(&self, table: &HashMap<K, Vec<&R>>, rows: &[L]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row1 in rows {
+            if let Some(rows2) = table.get(&(self.left_key)(row1)) {
+                for row2 in rows2 {
+                    result.push((self.mapper)(row1, row2));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Looks up each of ``rows`` in a ``table`` built over the left side.
+    fn probe_right(&self, table: &HashMap<K, Vec<&L>>, rows: &[R]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row2 in rows {
+            if let Some(rows1) = table.get(&(self.right_key)(row2)) {
+                for row1 in rows1 {
+                    result.push((self.mapper)(row1, row2));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Looks up each of ``rows`` in an ``index`` of the right side maintained incrementally.
+    fn probe_left_index(&self, index: &HashMap<K, HashMap<R, usize>>, rows: &[L]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row1 in rows {
+            for (row2, count) in index.get(&(self.left_key)(row1)).into_iter().flatten() {
+                result.extend(std::iter::repeat_n((self.mapper)(row1, row2), *count));
+            }
+        }
+
+        result
+    }
+
+    /// Looks up each of ``rows`` in an ``index`` of the left side maintained incrementally.
+    fn probe_right_index(&self, index: &HashMap<K, HashMap<L, usize>>, rows: &[R]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row2 in rows {
+            for (row1, count) in index.get(&(self.right_key)(row2)).into_iter().flatten() {
+                result.extend(std::iter::repeat_n((self.mapper)(row1, row2), *count));
+            }
+        }
+
+        result
+    }
+}
+
+impl<L, R, K, Res, EL, ER> Expression<Res> for HashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Clone + Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.combine(&self.left_expression.eval(), &self.right_expression.eval())
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        Ok(self.combine(
+            &self.left_expression.try_eval_with_context(context)?,
+            &self.right_expression.try_eval_with_context(context)?,
+        ))
+    }
+
+    /// Builds the hash table over the smaller input like ``eval``, so that tuples are
+    /// produced in the same order.
+    ///
+    /// ``right_expression`` is buffered in full, while ``left_expression`` is only
+    /// buffered until it has as many tuples as the right side. If it ends first, the
+    /// table is built over it as in ``combine``, otherwise the rest of it is streamed
+    /// against a table built over the right side.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Res> + 'a>
+    where
+        Res: 'a,
+    {
+        let right_result: Vec<R> = self.right_expression.iter().collect();
+        let mut left_rows = self.left_expression.iter();
+        let left_prefix: Vec<L> = left_rows.by_ref().take(right_result.len()).collect();
+
+        if left_prefix.len() < right_result.len() {
+            return Box::new(self.combine(&left_prefix, &right_result).into_iter());
+        }
+
+        let mut table: HashMap<K, Vec<R>> = HashMap::new();
+
+        for row2 in right_result {
+            table.entry((self.right_key)(&row2)).or_default().push(row2);
+        }
+
+        Box::new(left_prefix.into_iter().chain(left_rows).flat_map(move |row1| {
+            table
+                .get(&(self.left_key)(&row1))
+                .into_iter()
+                .flatten()
+                .map(|row2| (self.mapper)(&row1, row2))
+                .collect::<Vec<_>>()
+        }))
+    }
+}
+
+/// Groups references to ``rows`` by the key produced by ``key``.
+fn build_table<'a, T, K>(rows: &'a [T], key: &dyn Fn(&T) -> K) -> HashMap<K, Vec<&'a T>>
+where
+    K: Eq + Hash,
+{
+    let mut table: HashMap<K, Vec<&T>> = HashMap::new();
+
+    for row in rows {
+        table.entry(key(row)).or_default().push(row);
+    }
+
+    table
+}
+
+impl<L, R, K, Res, EL, ER> ParallelExpression<Res> for HashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    K: Clone + Eq + Hash + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently, then partitions the probe side across threads.
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        if left_result.len() < right_result.len() {
+            let table = build_table(&left_result, &*self.left_key);
+
+            par_chunks(right_result, threads, |rows| {
+                self.probe_right(&table, &rows)
+            })
+        } else {
+            let table = build_table(&right_result, &*self.right_key);
+
+            par_chunks(left_result, threads, |rows| self.probe_left(&table, &rows))
+        }
+    }
+}
+
+impl<L, R, K, Res, EL, ER> IncrementalExpression<Res> for HashJoin<L, R, K, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Hash,
+    R: Clone + Eq + PartialEq + Hash,
+    K: Clone + Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: IncrementalExpression<L>,
+    ER: IncrementalExpression<R>,
+{
+    /// The states of both sides, followed by the number of times each of their rows
+    /// occurs, grouped by key.
+    type State = (
+        EL::State,
+        ER::State,
+        HashMap<K, HashMap<L, usize>>,
+        HashMap<K, HashMap<R, usize>>,
+    );
+
+    fn init(&self) -> Self::State {
+        let mut left_index = HashMap::new();
+        let mut right_index = HashMap::new();
+
+        let left_rows = Delta::new(self.left_expression.eval(), Vec::new());
+        let right_rows = Delta::new(self.right_expression.eval(), Vec::new());
+        apply_to_index(&left_rows, &mut left_index, &*self.left_key);
+        apply_to_index(&right_rows, &mut right_index, &*self.right_key);
+
+        (
+            self.left_expression.init(),
+            self.right_expression.init(),
+            left_index,
+            right_index,
+        )
+    }
+
+    /// Applies the deletes of both sides before their inserts, looking each up in
+    /// the stored rows of the other side as they stand at that point, so that only
+    /// pairs that are present are deleted.
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<Res> {
+        let (left_state, right_state, left_index, right_index) = state;
+        let left_delta = self.left_expression.delta(left_state, changes);
+        let right_delta = self.right_expression.delta(right_state, changes);
+
+        let left_deleted = Delta::new(Vec::new(), left_delta.deleted);
+        let right_deleted = Delta::new(Vec::new(), right_delta.deleted);
+        let left_inserted = Delta::new(left_delta.inserted, Vec::new());
+        let right_inserted = Delta::new(right_delta.inserted, Vec::new());
+
+        let mut deleted = self.probe_left_index(right_index, &left_deleted.deleted);
+        apply_to_index(&left_deleted, left_index, &*self.left_key);
+        deleted.extend(self.probe_right_index(left_index, &right_deleted.deleted));
+        apply_to_index(&right_deleted, right_index, &*self.right_key);
+
+        let mut inserted = self.probe_left_index(right_index, &left_inserted.inserted);
+        apply_to_index(&left_inserted, left_index, &*self.left_key);
+        inserted.extend(self.probe_right_index(left_index, &right_inserted.inserted));
+        apply_to_index(&right_inserted, right_index, &*self.right_key);
+
+        Delta::new(inserted, deleted)
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.left_expression.apply(changes);
+        self.right_expression.apply(changes);
+    }
+}
+
+/// Applies ``delta`` to an ``index`` of the number of times each row occurs,
+/// grouped by the key produced by ``key``.
+fn apply_to_index<T, K>(
+    delta: &Delta<T>,
+    index: &mut HashMap<K, HashMap<T, usize>>,
+    key: &dyn Fn(&T) -> K,
+) where
+    T: Clone + Eq + PartialEq + Hash,
+    K: Eq + Hash,
+{
+    for row in &delta.inserted {
+        *index.entry(key(row)).or_default().entry(row.clone()).or_insert(0) += 1;
+    }
+
+    for row in &delta.deleted {
+        let key = key(row);
+
+        if let Some(rows) = index.get_mut(&key) {
+            Delta::new(Vec::new(), vec![row.clone()]).apply_to(rows);
+
+            if rows.is_empty() {
+                index.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Join, Terminal};
+
+    #[test]
+    fn join_on_key() {
+        let values1 = &[(1, "test string", 123), (2, "another string", 25)];
+        let values2 = &[("a", 1), ("b", 2)];
+
+        let expected_result = &[
+            (1, "test string", 123, "a", 1),
+            (2, "another string", 25, "b", 2),
+        ];
+
+        assert_eq!(
+            HashJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x| x.0,
+                |y| y.1,
+                |x, y| (x.0, x.1, x.2, y.0, y.1),
+            )
+            .eval(),
+            expected_result
+        );
+    }
+
+    #[test]
+    fn empty_join() {
+        let values1 = &[(1, "test string", 123), (2, "another string", 25)];
+        let values2 = &[("a", 3), ("b", 4)];
+
+        assert_eq!(
+            HashJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x| x.0,
+                |y| y.1,
+                |x, y| (x.0, x.1, x.2, y.0, y.1),
+            )
+            .eval(),
+            &[]
+        );
+    }
+
+    #[test]
+    fn matches_nested_loop_join() {
+        let values1 = &[(1, "a"), (2, "b"), (2, "c"), (3, "d")];
+        let values2 = &[(2, 10), (2, 20), (3, 30), (4, 40), (1, 50), (1, 60), (5, 70)];
+
+        let mut left_build = HashJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x| x.0,
+            |y| y.0,
+            |x, y| (x.1, y.1),
+        )
+        .eval();
+        let mut right_build = HashJoin::new(
+            Terminal::new(values2),
+            Terminal::new(values1),
+            |y| y.0,
+            |x| x.0,
+            |y, x| (x.1, y.1),
+        )
+        .eval();
+        let mut expected_result = Join::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x, y| x.0 == y.0,
+            |x, y| (x.1, y.1),
+        )
+        .eval();
+
+        left_build.sort();
+        right_build.sort();
+        expected_result.sort();
+
+        assert_eq!(left_build, expected_result);
+        assert_eq!(right_build, expected_result);
+    }
+
+    #[test]
+    fn iter_matches_eval() {
+        let values1 = &[(1, "a"), (2, "b"), (2, "c")];
+        let values2 = &[(2, 10), (1, 20), (2, 30), (1, 40)];
+
+        let left_build = HashJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x| x.0,
+            |y| y.0,
+            |x, y| (x.1, y.1),
+        );
+        let right_build = HashJoin::new(
+            Terminal::new(values2),
+            Terminal::new(values1),
+            |y| y.0,
+            |x| x.0,
+            |y, x| (x.1, y.1),
+        );
+
+        assert_eq!(
+            left_build.eval(),
+            &[("b", 10), ("c", 10), ("a", 20), ("b", 30), ("c", 30), ("a", 40)]
+        );
+        assert_eq!(left_build.iter().collect::<Vec<_>>(), left_build.eval());
+        assert_eq!(
+            right_build.eval(),
+            &[("b", 10), ("c", 10), ("a", 20), ("b", 30), ("c", 30), ("a", 40)]
+        );
+        assert_eq!(right_build.iter().collect::<Vec<_>>(), right_build.eval());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{Expression, Terminal};
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The rows inserted into and deleted from a relation, using bag semantics.
+///
+/// Deleted rows are removed before inserted rows are added, and deletes of rows
+/// that are not present are ignored. The deltas produced by expressions only
+/// delete rows that are present.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delta<S> {
+    pub inserted: Vec<S>,
+    pub deleted: Vec<S>,
+}
+
+impl<S> Delta<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    pub fn new(inserted: Vec<S>, deleted: Vec<S>) -> Self {
+        Self { inserted, deleted }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Vec::new(), Vec::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Appends the rows of ``other`` to this delta.
+    pub fn extend(&mut self, other: Delta<S>) {
+        self.inserted.extend(other.inserted);
+        self.deleted.extend(other.deleted);
+    }
+}
+
+impl<S> Delta<S>
+where
+    S: Clone + Eq + PartialEq + Hash,
+{
+    /// Applies the delta to a bag of rows stored as the number of times each row
+    /// occurs, removing rows whose count drops to zero from ``counts``.
+    pub fn apply_to(&self, counts: &mut HashMap<S, usize>) {
+        for row in &self.deleted {
+            if let Some(count) = counts.get_mut(row) {
+                *count -= 1;
+
+                if *count == 0 {
+                    counts.remove(row);
+                }
+            }
+        }
+
+        for row in &self.inserted {
+            *counts.entry(row.clone()).or_insert(0) += 1;
+        }
+    }
+}
+
+/// The deltas of a set of ``Terminal``s, keyed by the identity of each ``Terminal``.
+///
+/// A ``Terminal`` keeps its identity when cloned or updated, so the ``Terminal``
+/// used to build an expression can later be used to describe changes to it.
+#[derive(Default)]
+pub struct Changes {
+    deltas: HashMap<u64, Box<dyn Any>>,
+}
+
+impl Changes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records ``delta`` as the change to ``terminal``, replacing any previous delta.
+    pub fn insert<S>(&mut self, terminal: &Terminal<S>, delta: Delta<S>)
+    where
+        S: Clone + Eq + PartialEq + 'static,
+    {
+        self.deltas.insert(terminal.id(), Box::new(delta));
+    }
+
+    pub fn get<S>(&self, terminal: &Terminal<S>) -> Option<&Delta<S>>
+    where
+        S: Clone + Eq + PartialEq + 'static,
+    {
+        self.deltas
+            .get(&terminal.id())
+            .and_then(|delta| delta.downcast_ref())
+    }
+}
+
+/// Counts the number of times each of ``rows`` occurs.
+pub(crate) fn counts<S>(rows: &[S]) -> HashMap<S, usize>
+where
+    S: Clone + Eq + PartialEq + Hash,
+{
+    let mut counts = HashMap::new();
+    Delta::new(rows.to_vec(), Vec::new()).apply_to(&mut counts);
+
+    counts
+}
+
+/// Defines expressions whose result can be maintained from changes to their ``Terminal``s
+/// without being evaluated again.
+///
+/// Implemented by Terminal, Selection, Projection, Join, HashJoin, Union, Intersection
+/// and CartesianProduct. Binary operators keep the rows of both inputs in their
+/// ``State`` and probe them with each delta, so no input is evaluated after ``init``.
+/// HashJoin indexes the rows by key and Intersection by row, doing work proportional
+/// to the delta. Join and CartesianProduct test each row of a delta against every
+/// distinct row of the other input, since their predicate cannot be indexed.
+pub trait IncrementalExpression<Output>: Expression<Output>
+where
+    Output: Clone + Eq + PartialEq,
+{
+    /// The state kept between changes, such as the rows of the inputs of a join.
+    type State: Clone;
+
+    /// Builds the state for the current contents of the ``Terminal``s.
+    fn init(&self) -> Self::State;
+
+    /// Computes the change to the result caused by ``changes`` and updates ``state``
+    /// with it. The ``Terminal``s of the expression are not read.
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<Output>;
+
+    /// Applies ``changes`` to the ``Terminal``s of the expression.
+    fn apply(&mut self, changes: &Changes);
+}
+
+/// Keeps the result of ``expression`` up to date as its ``Terminal``s change.
+///
+/// The result is maintained as a bag, storing the number of times each row occurs,
+/// so the order of its rows may differ from the order produced by ``eval``.
+#[derive(Clone)]
+pub struct MaintainedView<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: IncrementalExpression<S>,
+{
+    expression: E,
+    state: E::State,
+    result: HashMap<S, usize>,
+}
+
+impl<S, E> MaintainedView<S, E>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E: IncrementalExpression<S>,
+{
+    /// Evaluates ``expression`` once to obtain the initial result and the state of its operators.
+    pub fn new(expression: E) -> Self {
+        Self {
+            state: expression.init(),
+            result: counts(&expression.eval()),
+            expression,
+        }
+    }
+
+    /// Returns the rows of the result, each repeated as many times as it occurs.
+    pub fn result(&self) -> Vec<S> {
+        self.result
+            .iter()
+            .flat_map(|(row, count)| std::iter::repeat_n(row.clone(), *count))
+            .collect()
+    }
+
+    /// Returns the number of times each row occurs in the result.
+    pub fn counts(&self) -> &HashMap<S, usize> {
+        &self.result
+    }
+
+    /// Returns the expression with all changes applied so far.
+    pub fn expression(&self) -> &E {
+        &self.expression
+    }
+
+    /// Updates the result with ``changes``, returning the change to the result.
+    pub fn update(&mut self, changes: &Changes) -> Delta<S> {
+        let delta = self.expression.delta(&mut self.state, changes);
+
+        self.expression.apply(changes);
+        delta.apply_to(&mut self.result);
+
+        delta
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CartesianProduct, HashJoin, Intersection, Join, Projection, Semantics};
+
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn sorted_result<S, E>(view: &MaintainedView<S, E>) -> Vec<S>
+    where
+        S: Clone + Eq + PartialEq + Hash + Ord + Debug,
+        E: IncrementalExpression<S>,
+    {
+        let mut result = view.result();
+        let mut expected = view.expression().eval();
+        result.sort();
+        expected.sort();
+
+        assert_eq!(result, expected);
+
+        result
+    }
+
+    #[test]
+    fn apply_delta() {
+        let mut counts = HashMap::from([(1, 1), (2, 2), (3, 1)]);
+
+        Delta::new(vec![4, 2], vec![2, 3, 5]).apply_to(&mut counts);
+
+        assert_eq!(counts, HashMap::from([(1, 1), (2, 2), (4, 1)]));
+    }
+
+    #[test]
+    fn changes_follow_terminal_identity() {
+        let terminal = Terminal::new(&[1, 2]);
+        let other = Terminal::new(&[1, 2]);
+        let mut changes = Changes::new();
+
+        changes.insert(&terminal, Delta::new(vec![3], vec![]));
+
+        assert_eq!(
+            changes.get(&terminal.clone()),
+            Some(&Delta::new(vec![3], vec![]))
+        );
+        assert_eq!(changes.get(&other), None);
+    }
+
+    #[test]
+    fn binary_deltas_do_not_evaluate_inputs() {
+        static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let left = Terminal::new(&[1, 2, 2, 3]);
+        let right = Projection::new(Terminal::new(&[2, 3, 3, 4]), |x| {
+            EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+            *x
+        });
+
+        let mut join = MaintainedView::new(Join::new(
+            left.clone(),
+            right.clone(),
+            |x, y| x == y,
+            |x, y| (*x, *y),
+        ));
+        let mut hash_join = MaintainedView::new(HashJoin::new(
+            left.clone(),
+            right.clone(),
+            |x| *x,
+            |y| *y,
+            |x, y| (*x, *y),
+        ));
+        let mut product =
+            MaintainedView::new(CartesianProduct::new(left.clone(), right.clone(), |x, y| {
+                (*x, *y)
+            }));
+        let mut intersection = MaintainedView::new(Intersection::new(left.clone(), right));
+
+        let mut changes = Changes::new();
+        changes.insert(&left, Delta::new(vec![4, 3], vec![2, 1]));
+
+        EVALUATIONS.store(0, Ordering::SeqCst);
+        join.update(&changes);
+        hash_join.update(&changes);
+        product.update(&changes);
+        intersection.update(&changes);
+        assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 0);
+
+        let expected = &[(2, 2), (3, 3), (3, 3), (3, 3), (3, 3), (4, 4)];
+        assert_eq!(sorted_result(&join), expected);
+        assert_eq!(sorted_result(&hash_join), expected);
+        assert_eq!(sorted_result(&product).len(), 16);
+        assert_eq!(sorted_result(&intersection), &[2, 3, 3, 4]);
+    }
+
+    #[test]
+    fn both_sides_changed_together() {
+        let left = Terminal::new(&[1, 2, 2]);
+        let right = Terminal::new(&[2, 3]);
+
+        let mut join = MaintainedView::new(Join::new(
+            left.clone(),
+            right.clone(),
+            |x, y| x <= y,
+            |x, y| (*x, *y),
+        ));
+        let mut hash_join = MaintainedView::new(HashJoin::new(
+            left.clone(),
+            right.clone(),
+            |x| *x,
+            |y| *y,
+            |x, y| (*x, *y),
+        ));
+        let mut intersection = MaintainedView::new(Intersection::with_semantics(
+            left.clone(),
+            right.clone(),
+            Semantics::Set,
+        ));
+
+        let mut changes = Changes::new();
+        changes.insert(&left, Delta::new(vec![3], vec![2]));
+        changes.insert(&right, Delta::new(vec![1, 2], vec![3]));
+
+        join.update(&changes);
+        hash_join.update(&changes);
+        intersection.update(&changes);
+
+        assert_eq!(
+            sorted_result(&join),
+            &[(1, 1), (1, 2), (1, 2), (2, 2), (2, 2)]
+        );
+        assert_eq!(sorted_result(&hash_join), &[(1, 1), (2, 2), (2, 2)]);
+        assert_eq!(sorted_result(&intersection), &[1, 2]);
+    }
+
+    #[test]
+    fn deletes_applied_before_inserts() {
+        let terminal = Terminal::new(&[1, 2]);
+        let mut view = MaintainedView::new(terminal.clone());
+
+        let mut changes = Changes::new();
+        changes.insert(&terminal, Delta::new(vec![5], vec![5]));
+
+        assert_eq!(view.update(&changes), Delta::new(vec![5], vec![]));
+        assert_eq!(sorted_result(&view), &[1, 2, 5]);
+    }
+
+    #[test]
+    fn deletes_of_missing_rows_ignored() {
+        let terminal = Terminal::new(&[2]);
+        let mut view = MaintainedView::new(Projection::new(terminal.clone(), |x| x % 2));
+
+        let mut changes = Changes::new();
+        changes.insert(&terminal, Delta::new(vec![], vec![4]));
+
+        assert_eq!(view.update(&changes), Delta::empty());
+        assert_eq!(sorted_result(&view), &[0]);
+    }
+}
@@ -1,69 +1,223 @@
-use crate::Expression;
+use crate::incremental::counts;
+use crate::parallel::par_join;
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+    Semantics,
+};
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::iter::FromIterator;
 use std::marker::PhantomData;
 
 /// Keeps only the tuples that are in both ``left_expression`` and ``right_expression``
 /// 
-/// Note that this is operation uses bag semantics and so duplicates can
-// appear in the result.
+/// Under bag semantics (``INTERSECT ALL``) a tuple occurring ``m`` times on the left
+/// and ``n`` times on the right appears ``min(m, n)`` times in the result.
+/// Under set semantics (``INTERSECT``) the result contains no duplicates.
 #[derive(Clone)]
 pub struct Intersection<S: Clone, E1, E2>
 where
-    S: Clone + Eq + PartialEq,
+    S: Clone + Eq + PartialEq + Hash,
     E1: Expression<S>,
     E2: Expression<S>,
 {
     pub left_expression: E1,
     pub right_expression: E2,
+    pub semantics: Semantics,
     phantom: PhantomData<S>,
 }
 
 impl<S, E1, E2> Intersection<S, E1, E2>
 where
-    S: Clone + Eq + PartialEq,
+    S: Clone + Eq + PartialEq + Hash,
     E1: Expression<S>,
     E2: Expression<S>,
 {
     pub fn new(left_expression: E1, right_expression: E2) -> Self {
+        Self::with_semantics(left_expression, right_expression, Semantics::Bag)
+    }
+
+    pub fn with_semantics(left_expression: E1, right_expression: E2, semantics: Semantics) -> Self {
         Self {
             left_expression,
             right_expression,
+            semantics,
             phantom: PhantomData,
         }
     }
+
+    /// Returns the number of times ``row`` occurs in the intersection of two bags of
+    /// rows stored as the number of times each row occurs.
+    fn multiplicity(
+        &self,
+        left_rows: &HashMap<S, usize>,
+        right_rows: &HashMap<S, usize>,
+        row: &S,
+    ) -> usize {
+        let left = left_rows.get(row).copied().unwrap_or_default();
+        let right = right_rows.get(row).copied().unwrap_or_default();
+
+        match self.semantics {
+            Semantics::Bag => left.min(right),
+            Semantics::Set => left.min(right).min(1),
+        }
+    }
+
+    /// Filters the tuples of ``left_rows`` against the evaluated ``right_result``.
+    fn combine<'a>(
+        &self,
+        left_rows: impl Iterator<Item = S> + 'a,
+        right_result: Vec<S>,
+    ) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(left_rows.filter(self.matcher(right_result)))
+    }
+
+    /// Returns a predicate accepting the left tuples that belong in the result,
+    /// which must be called on the left tuples in order.
+    fn matcher<'a>(&self, right_result: Vec<S>) -> Box<dyn FnMut(&S) -> bool + 'a>
+    where
+        S: 'a,
+    {
+        match self.semantics {
+            Semantics::Bag => {
+                let mut counts: HashMap<S, usize> = HashMap::new();
+                for row in right_result {
+                    *counts.entry(row).or_insert(0) += 1;
+                }
+
+                Box::new(move |row| match counts.get_mut(row) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                })
+            }
+            Semantics::Set => {
+                let mut remaining: HashSet<S> = right_result.into_iter().collect();
+
+                Box::new(move |row| remaining.remove(row))
+            }
+        }
+    }
 }
 
 impl<S, E1, E2> Expression<S> for Intersection<S, E1, E2>
 where
-    S: Clone + Eq + PartialEq,
+    S: Clone + Eq + PartialEq + Hash,
     E1: Expression<S>,
     E2: Expression<S>,
 {
     fn eval(&self) -> Vec<S> {
-        let left_result = (self.left_expression).eval();
-        let right_result = (self.right_expression).eval();
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let left_result = (self.left_expression).try_eval_with_context(context)?;
+        let right_result = (self.right_expression).try_eval_with_context(context)?;
 
+        let mut matches = self.matcher(right_result);
         let mut result = Vec::new();
 
-        for row1 in &left_result {
-            for row2 in &right_result {
-                if row1 == row2 {
-                    result.push(row1.clone());
-                }
+        for row in left_result {
+            if matches(&row) {
+                result.push(row);
             }
+
+            context.check("Intersection", result.len())?;
         }
 
-        result
+        Ok(result)
+    }
+
+    /// Buffers ``right_expression`` and streams ``left_expression`` against it.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        self.combine(self.left_expression.iter(), (self.right_expression).eval())
+    }
+}
+
+impl<S, E1, E2> ParallelExpression<S> for Intersection<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash + Send + Sync,
+    E1: ParallelExpression<S>,
+    E2: ParallelExpression<S>,
+{
+    /// Evaluates both sides concurrently.
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        self.combine(left_result.into_iter(), right_result).collect()
+    }
+}
+
+impl<S, E1, E2> IncrementalExpression<S> for Intersection<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Hash,
+    E1: IncrementalExpression<S>,
+    E2: IncrementalExpression<S>,
+{
+    /// The states of both sides, followed by the number of times each of their rows occurs.
+    type State = (E1::State, E2::State, HashMap<S, usize>, HashMap<S, usize>);
+
+    fn init(&self) -> Self::State {
+        (
+            self.left_expression.init(),
+            self.right_expression.init(),
+            counts(&self.left_expression.eval()),
+            counts(&self.right_expression.eval()),
+        )
+    }
+
+    /// Compares the multiplicity of each affected tuple before and after the change.
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<S> {
+        let (left_state, right_state, left_rows, right_rows) = state;
+        let left_delta = self.left_expression.delta(left_state, changes);
+        let right_delta = self.right_expression.delta(right_state, changes);
+
+        let old: HashMap<S, usize> = [&left_delta, &right_delta]
+            .iter()
+            .flat_map(|delta| delta.inserted.iter().chain(&delta.deleted))
+            .map(|row| (row.clone(), self.multiplicity(left_rows, right_rows, row)))
+            .collect();
+
+        left_delta.apply_to(left_rows);
+        right_delta.apply_to(right_rows);
+
+        let mut delta = Delta::empty();
+
+        for (row, old) in old {
+            let new = self.multiplicity(left_rows, right_rows, &row);
+
+            if new > old {
+                delta.inserted.extend(std::iter::repeat_n(row, new - old));
+            } else {
+                delta.deleted.extend(std::iter::repeat_n(row, old - new));
+            }
+        }
+
+        delta
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.left_expression.apply(changes);
+        self.right_expression.apply(changes);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Terminal;
+    use crate::{CancellationToken, LimitExceeded, Terminal};
 
     #[test]
     fn empty_intersection() {
@@ -131,4 +285,81 @@ mod test {
             expected_result
         );
     }
+
+    #[test]
+    fn bag_intersection_keeps_min_multiplicity() {
+        let values1 = &[1, 1, 1, 2, 2, 3];
+        let values2 = &[1, 1, 2, 2, 2, 4];
+
+        assert_eq!(
+            Intersection::new(Terminal::new(values1), Terminal::new(values2)).eval(),
+            &[1, 1, 2, 2]
+        );
+    }
+
+    #[test]
+    fn bag_intersection_duplicates_on_one_side() {
+        let values1 = &[5, 5, 5, 5];
+        let values2 = &[5];
+
+        assert_eq!(
+            Intersection::new(Terminal::new(values1), Terminal::new(values2)).eval(),
+            &[5]
+        );
+        assert_eq!(
+            Intersection::new(Terminal::new(values2), Terminal::new(values1)).eval(),
+            &[5]
+        );
+    }
+
+    #[test]
+    fn set_intersection_removes_duplicates() {
+        let values1 = &[1, 1, 1, 2, 2, 3];
+        let values2 = &[2, 1, 1, 2, 2, 4];
+
+        assert_eq!(
+            Intersection::with_semantics(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                Semantics::Set
+            )
+            .eval(),
+            &[1, 2]
+        );
+    }
+
+    #[test]
+    fn cancelled_intersection() {
+        let values = &[1, 2, 3];
+        let token = CancellationToken::new();
+        let context = EvalContext::new().with_cancellation(token.clone());
+        let intersection = Intersection::new(Terminal::new(values), Terminal::new(values));
+
+        assert_eq!(intersection.try_eval_with_context(&context).unwrap(), &[1, 2, 3]);
+
+        token.cancel();
+        let error = intersection.try_eval_with_context(&context).unwrap_err();
+
+        assert_eq!(error.operator, "Intersection");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Cancelled)
+        );
+    }
+
+    #[test]
+    fn intersection_delta() {
+        let left = Terminal::new(&[1, 1, 2]);
+        let right = Terminal::new(&[1, 2, 2]);
+        let intersection = Intersection::new(left.clone(), right.clone());
+
+        let mut changes = Changes::new();
+        changes.insert(&left, Delta::new(vec![2], vec![1]));
+        changes.insert(&right, Delta::new(vec![3], vec![]));
+
+        let delta = intersection.delta(&mut intersection.init(), &changes);
+
+        assert_eq!(delta.inserted, &[2]);
+        assert!(delta.deleted.is_empty());
+    }
 }
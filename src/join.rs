@@ -1,4 +1,12 @@
-use crate::Expression;
+use crate::incremental::counts;
+use crate::parallel::{par_chunks, par_join};
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
 
 /// Keeps all ``(left_expression, right_expression)`` pairs the match some condition ``predicate``.
 /// 
@@ -16,8 +24,8 @@ where
 {
     pub left_expression: EL,
     pub right_expression: ER,
-    pub predicate: fn(&L, &R) -> bool,
-    pub mapper: fn(&L, &R) -> Res,
+    pub predicate: Arc<dyn Fn(&L, &R) -> bool + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R) -> Res + Send + Sync>,
 }
 
 impl<L, R, Res, EL, ER> Join<L, R, Res, EL, ER>
@@ -31,15 +39,70 @@ where
     pub fn new(
         left_expression: EL,
         right_expression: ER,
-        predicate: fn(&L, &R) -> bool,
-        mapper: fn(&L, &R) -> Res,
+        predicate: impl Fn(&L, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R) -> Res + Send + Sync + 'static,
     ) -> Self {
         Self {
             left_expression,
             right_expression,
-            predicate,
-            mapper,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins two bags of rows stored as the number of times each row occurs.
+    fn join_counts(
+        &self,
+        left_rows: &HashMap<L, usize>,
+        right_rows: &HashMap<R, usize>,
+    ) -> Vec<Res>
+    where
+        L: Hash,
+        R: Hash,
+    {
+        let mut result = Vec::new();
+
+        for (row1, count1) in left_rows {
+            for (row2, count2) in right_rows {
+                if (self.predicate)(row1, row2) {
+                    let row = (self.mapper)(row1, row2);
+                    result.extend(std::iter::repeat_n(row, count1 * count2));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Joins the evaluated results of both sides, stopping as ``operator`` once
+    /// a limit of ``context`` is exceeded.
+    pub(crate) fn combine(
+        &self,
+        left_result: &[L],
+        right_result: &[R],
+        context: &EvalContext,
+        operator: &'static str,
+    ) -> Result<Vec<Res>, EvalError> {
+        let mut result = Vec::new();
+
+        for row1 in left_result {
+            context.check(operator, result.len())?;
+
+            for row2 in right_result {
+                if (self.predicate)(row1, row2) {
+                    result.push((self.mapper)(row1, row2));
+                    context.check_rows(operator, result.len())?;
+                }
+            }
         }
+
+        Ok(result)
+    }
+
+    /// Joins the evaluated results of both sides without any limits.
+    fn join_all(&self, left_result: &[L], right_result: &[R]) -> Vec<Res> {
+        self.combine(left_result, right_result, &EvalContext::default(), "Join")
+            .expect("an unlimited context never stops evaluation")
     }
 }
 
@@ -52,27 +115,113 @@ where
     ER: Expression<R>,
 {
     fn eval(&self) -> Vec<Res> {
-        let left_result = self.left_expression.eval();
+        self.join_all(&self.left_expression.eval(), &self.right_expression.eval())
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        self.combine(
+            &self.left_expression.try_eval_with_context(context)?,
+            &self.right_expression.try_eval_with_context(context)?,
+            context,
+            "Join",
+        )
+    }
+
+    /// Buffers ``right_expression`` and streams ``left_expression`` against it.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Res> + 'a>
+    where
+        Res: 'a,
+    {
         let right_result = self.right_expression.eval();
 
-        let mut result = Vec::new();
+        Box::new(self.left_expression.iter().flat_map(move |row1| {
+            right_result
+                .iter()
+                .filter(|row2| (self.predicate)(&row1, row2))
+                .map(|row2| (self.mapper)(&row1, row2))
+                .collect::<Vec<_>>()
+        }))
+    }
+}
 
-        for row1 in &left_result {
-            for row2 in &right_result {
-                if (self.predicate)(&row1, &row2) {
-                    result.push((self.mapper)(&row1, &row2));
-                }
-            }
-        }
+impl<L, R, Res, EL, ER> ParallelExpression<Res> for Join<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently, then partitions ``left_expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
 
-        result
+        par_chunks(left_result, threads, |rows| {
+            self.join_all(&rows, &right_result)
+        })
+    }
+}
+
+impl<L, R, Res, EL, ER> IncrementalExpression<Res> for Join<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Hash,
+    R: Clone + Eq + PartialEq + Hash,
+    Res: Clone + Eq + PartialEq,
+    EL: IncrementalExpression<L>,
+    ER: IncrementalExpression<R>,
+{
+    /// The states of both sides, followed by the number of times each of their rows occurs.
+    type State = (EL::State, ER::State, HashMap<L, usize>, HashMap<R, usize>);
+
+    fn init(&self) -> Self::State {
+        (
+            self.left_expression.init(),
+            self.right_expression.init(),
+            counts(&self.left_expression.eval()),
+            counts(&self.right_expression.eval()),
+        )
+    }
+
+    /// Applies the deletes of both sides before their inserts, joining each with the
+    /// stored rows of the other side as they stand at that point, so that only pairs
+    /// that are present are deleted.
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<Res> {
+        let (left_state, right_state, left_rows, right_rows) = state;
+        let left_delta = self.left_expression.delta(left_state, changes);
+        let right_delta = self.right_expression.delta(right_state, changes);
+
+        let left_deleted = Delta::new(Vec::new(), left_delta.deleted);
+        let right_deleted = Delta::new(Vec::new(), right_delta.deleted);
+        let left_inserted = Delta::new(left_delta.inserted, Vec::new());
+        let right_inserted = Delta::new(right_delta.inserted, Vec::new());
+
+        let mut deleted = self.join_counts(&counts(&left_deleted.deleted), right_rows);
+        left_deleted.apply_to(left_rows);
+        deleted.extend(self.join_counts(left_rows, &counts(&right_deleted.deleted)));
+        right_deleted.apply_to(right_rows);
+
+        let mut inserted = self.join_counts(&counts(&left_inserted.inserted), right_rows);
+        left_inserted.apply_to(left_rows);
+        inserted.extend(self.join_counts(left_rows, &counts(&right_inserted.inserted)));
+        right_inserted.apply_to(right_rows);
+
+        Delta::new(inserted, deleted)
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.left_expression.apply(changes);
+        self.right_expression.apply(changes);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Terminal;
+    use crate::{LimitExceeded, Terminal};
 
     #[test]
     fn cartesian_product_homogenous_types() {
@@ -153,11 +302,49 @@ mod test {
             Join::new(
                 Terminal::new(values1),
                 Terminal::new(values2),
-                |x, y| false,
+                |_, _| false,
                 |x, y| (x.0, x.1, x.2, y.0, y.1),
             )
             .eval(),
             &[]
         );
     }
+
+    #[test]
+    fn join_stops_at_row_limit() {
+        let values = &[1, 2, 3, 4];
+        let context = EvalContext::new().with_max_rows(5);
+
+        let error = Join::new(
+            Terminal::new(values),
+            Terminal::new(values),
+            |x, y| x <= y,
+            |x, y| (*x, *y),
+        )
+        .try_eval_with_context(&context)
+        .unwrap_err();
+
+        assert_eq!(error.operator, "Join");
+        assert_eq!(
+            error.source.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Rows(5))
+        );
+    }
+
+    #[test]
+    fn join_delta() {
+        let left = Terminal::new(&[(1, "a"), (2, "b")]);
+        let right = Terminal::new(&[(1, "x"), (2, "y")]);
+        let join = Join::new(left.clone(), right.clone(), |x, y| x.0 == y.0, |x, y| (x.1, y.1));
+
+        let mut changes = Changes::new();
+        changes.insert(&left, Delta::new(vec![(2, "c")], vec![(1, "a")]));
+        changes.insert(&right, Delta::new(vec![(2, "z")], vec![]));
+
+        let mut delta = join.delta(&mut join.init(), &changes);
+        delta.inserted.sort();
+
+        assert_eq!(delta.inserted, &[("b", "z"), ("c", "y"), ("c", "z")]);
+        assert_eq!(delta.deleted, &[("a", "x")]);
+    }
 }
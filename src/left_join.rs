@@ -0,0 +1,189 @@
+use crate::parallel::{par_chunks, par_join};
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match some condition ``predicate``,
+/// as well as every tuple in ``left_expression`` that matches no tuple in ``right_expression``.
+///
+/// Unmatched left tuples are passed to the mapper with ``None`` in place of the right tuple.
+#[derive(Clone)]
+pub struct LeftJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> bool + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, Option<&R>) -> Res + Send + Sync>,
+}
+
+impl<L, R, Res, EL, ER> LeftJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&L, Option<&R>) -> Res + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins the evaluated results of both sides.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row1 in left_result {
+            let mut matched = false;
+
+            for row2 in right_result {
+                if (self.predicate)(row1, row2) {
+                    matched = true;
+                    result.push((self.mapper)(row1, Some(row2)));
+                }
+            }
+
+            if !matched {
+                result.push((self.mapper)(row1, None));
+            }
+        }
+
+        result
+    }
+}
+
+impl<L, R, Res, EL, ER> Expression<Res> for LeftJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.combine(&self.left_expression.eval(), &self.right_expression.eval())
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        Ok(self.combine(
+            &self.left_expression.try_eval_with_context(context)?,
+            &self.right_expression.try_eval_with_context(context)?,
+        ))
+    }
+
+    /// Buffers ``right_expression`` and streams ``left_expression`` against it.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Res> + 'a>
+    where
+        Res: 'a,
+    {
+        let right_result = self.right_expression.eval();
+
+        Box::new(self.left_expression.iter().flat_map(move |row1| {
+            let mut matches: Vec<Res> = right_result
+                .iter()
+                .filter(|row2| (self.predicate)(&row1, row2))
+                .map(|row2| (self.mapper)(&row1, Some(row2)))
+                .collect();
+
+            if matches.is_empty() {
+                matches.push((self.mapper)(&row1, None));
+            }
+
+            matches
+        }))
+    }
+}
+
+impl<L, R, Res, EL, ER> ParallelExpression<Res> for LeftJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently, then partitions ``left_expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        par_chunks(left_result, threads, |rows| {
+            self.combine(&rows, &right_result)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn all_rows_matched() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            LeftJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.1, y.map(|y| y.1)),
+            )
+            .eval(),
+            &[("a", Some("x")), ("b", Some("y"))]
+        );
+    }
+
+    #[test]
+    fn unmatched_left_rows() {
+        let values1 = &[(1, "a"), (2, "b"), (3, "c")];
+        let values2 = &[(2, "y"), (4, "z")];
+
+        assert_eq!(
+            LeftJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.1, y.map(|y| y.1)),
+            )
+            .eval(),
+            &[("a", None), ("b", Some("y")), ("c", None)]
+        );
+    }
+
+    #[test]
+    fn duplicate_matches() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[(1, "x"), (1, "y")];
+
+        assert_eq!(
+            LeftJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.1, y.map(|y| y.1)),
+            )
+            .eval(),
+            &[("a", Some("x")), ("a", Some("y")), ("b", None)]
+        );
+    }
+}
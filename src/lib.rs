@@ -31,22 +31,118 @@
 //! 
 //! ```
 
+#![allow(clippy::type_complexity)]
+// The ``ra!`` macro matches the Greek letters σ and π.
+#![allow(mixed_script_confusables)]
+
+// Allows the code generated by ``#[derive(Relation)]`` to be used within this crate.
+extern crate self as ra_evaluator;
+
+mod error;
+mod eval_context;
 mod expression;
 mod expression_builder;
 mod projection;
 mod selection;
 mod terminal;
+mod generator;
 mod join;
 mod union;
 mod intersection;
 mod cartesian_product;
+mod cached;
+mod boxed;
+mod try_selection;
+mod try_projection;
+mod try_join;
+mod try_hash_join;
+mod try_left_join;
+mod try_right_join;
+mod try_full_join;
+mod try_semi_join;
+mod try_anti_join;
+mod try_group_by;
+mod try_sort;
+mod try_division;
+mod param_selection;
+mod param_projection;
+mod param_join;
+mod difference;
+mod distinct;
+mod division;
+mod fixpoint;
+mod hash_join;
+mod left_join;
+mod right_join;
+mod full_join;
+mod semi_join;
+mod anti_join;
+mod aggregate;
+mod group_by;
+mod sort;
+mod limit;
+mod offset;
+mod parallel;
+mod incremental;
+mod spill;
+mod external_sort;
+mod external_hash_join;
+mod external_distinct;
+mod semantics;
+mod relation;
+mod ra;
 
+pub use error::*;
+pub use eval_context::*;
 pub use expression::*;
 pub use expression_builder::*;
 pub use projection::*;
 pub use selection::*;
 pub use terminal::*;
+pub use generator::*;
 pub use join::*;
 pub use union::*;
 pub use intersection::*;
-pub use cartesian_product::*;
\ No newline at end of file
+pub use cartesian_product::*;
+pub use cached::*;
+pub use boxed::*;
+pub use try_selection::*;
+pub use try_projection::*;
+pub use try_join::*;
+pub use try_hash_join::*;
+pub use try_left_join::*;
+pub use try_right_join::*;
+pub use try_full_join::*;
+pub use try_semi_join::*;
+pub use try_anti_join::*;
+pub use try_group_by::*;
+pub use try_sort::*;
+pub use try_division::*;
+pub use param_selection::*;
+pub use param_projection::*;
+pub use param_join::*;
+pub use difference::*;
+pub use distinct::*;
+pub use division::*;
+pub use fixpoint::*;
+pub use hash_join::*;
+pub use left_join::*;
+pub use right_join::*;
+pub use full_join::*;
+pub use semi_join::*;
+pub use anti_join::*;
+pub use aggregate::*;
+pub use group_by::*;
+pub use sort::*;
+pub use limit::*;
+pub use offset::*;
+pub use parallel::ParallelExpression;
+pub use incremental::*;
+pub use spill::{Spill, SpillConfig};
+pub use external_sort::*;
+pub use external_hash_join::*;
+pub use external_distinct::*;
+pub use semantics::*;
+pub use relation::*;
+
+pub use ra_evaluator_derive::Relation;
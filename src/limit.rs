@@ -0,0 +1,86 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::marker::PhantomData;
+
+/// Keeps at most the first ``count`` tuples in ``expression``.
+#[derive(Clone)]
+pub struct Limit<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub count: usize,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E> Limit<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, count: usize) -> Self {
+        Self {
+            expression,
+            count,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, E> Expression<S> for Limit<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut result = self.expression.try_eval_with_context(context)?;
+        result.truncate(self.count);
+        Ok(result)
+    }
+
+    /// Stops pulling from ``expression`` once ``count`` tuples have been produced.
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(self.expression.iter().take(self.count))
+    }
+}
+
+impl<S, E> ParallelExpression<S> for Limit<S, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let mut result = self.expression.par_eval(threads);
+        result.truncate(self.count);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn limit_some_rows() {
+        assert_eq!(Limit::new(Terminal::new(&[1, 2, 3, 4]), 2).eval(), &[1, 2]);
+    }
+
+    #[test]
+    fn limit_zero_rows() {
+        assert_eq!(Limit::new(Terminal::new(&[1, 2, 3, 4]), 0).eval(), &[]);
+    }
+
+    #[test]
+    fn limit_exceeds_rows() {
+        assert_eq!(Limit::new(Terminal::new(&[1, 2]), 5).eval(), &[1, 2]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::marker::PhantomData;
+
+/// Skips the first ``count`` tuples in ``expression``.
+#[derive(Clone)]
+pub struct Offset<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub count: usize,
+    phantom: PhantomData<S>,
+}
+
+impl<S, E> Offset<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, count: usize) -> Self {
+        Self {
+            expression,
+            count,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, E> Expression<S> for Offset<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        Ok(self
+            .expression
+            .try_eval_with_context(context)?
+            .into_iter()
+            .skip(self.count)
+            .collect())
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(self.expression.iter().skip(self.count))
+    }
+}
+
+impl<S, E> ParallelExpression<S> for Offset<S, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        self.expression
+            .par_eval(threads)
+            .into_iter()
+            .skip(self.count)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn skip_some_rows() {
+        assert_eq!(Offset::new(Terminal::new(&[1, 2, 3, 4]), 2).eval(), &[3, 4]);
+    }
+
+    #[test]
+    fn skip_zero_rows() {
+        assert_eq!(Offset::new(Terminal::new(&[1, 2, 3, 4]), 0).eval(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn skip_all_rows() {
+        assert_eq!(Offset::new(Terminal::new(&[1, 2]), 5).eval(), &[]);
+    }
+}
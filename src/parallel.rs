@@ -0,0 +1,130 @@
+use crate::Expression;
+
+use std::thread;
+
+/// Inputs smaller than this are not split across threads, as the cost of
+/// spawning a thread would outweigh the work it performs.
+const MIN_PARTITION_SIZE: usize = 1024;
+
+/// Defines expressions that can be evaluated across multiple threads.
+///
+/// Independent sub-expressions (such as both sides of a Join) are evaluated
+/// concurrently, and large inputs to row-at-a-time operations (such as Selection)
+/// are partitioned across worker threads. The result is identical to that of
+/// ``eval``, including its order.
+///
+/// The default implementation falls back on ``eval``.
+pub trait ParallelExpression<Output>: Expression<Output> + Sync
+where
+    Output: Clone + Eq + PartialEq + Send,
+{
+    /// Evaluates the expression using at most ``threads`` threads.
+    fn par_eval(&self, _threads: usize) -> Vec<Output> {
+        self.eval()
+    }
+}
+
+/// Runs ``left`` and ``right`` concurrently, splitting ``threads`` between them.
+///
+/// Returns the thread budget available to each side along with the results.
+pub(crate) fn par_join<A, B>(
+    threads: usize,
+    left: impl FnOnce(usize) -> A + Send,
+    right: impl FnOnce(usize) -> B + Send,
+) -> (A, B)
+where
+    A: Send,
+    B: Send,
+{
+    if threads <= 1 {
+        return (left(1), right(1));
+    }
+
+    let left_threads = threads.div_ceil(2);
+    let right_threads = threads - left_threads;
+
+    thread::scope(|scope| {
+        let right_handle = scope.spawn(move || right(right_threads));
+        let left_result = left(left_threads);
+
+        (
+            left_result,
+            right_handle.join().expect("worker thread panicked"),
+        )
+    })
+}
+
+/// Partitions ``rows`` into contiguous chunks, applies ``f`` to each chunk on its own
+/// thread and concatenates the results in the original order.
+pub(crate) fn par_chunks<T, U>(
+    rows: Vec<T>,
+    threads: usize,
+    f: impl Fn(Vec<T>) -> Vec<U> + Sync,
+) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+{
+    let partitions = threads.min(rows.len() / MIN_PARTITION_SIZE).max(1);
+
+    if partitions == 1 {
+        return f(rows);
+    }
+
+    let chunk_size = rows.len().div_ceil(partitions);
+    let mut chunks = Vec::with_capacity(partitions);
+    let mut rows = rows.into_iter();
+
+    loop {
+        let chunk: Vec<T> = rows.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    let f = &f;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || f(chunk)))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chunks_preserve_order() {
+        let values: Vec<usize> = (0..10_000).collect();
+
+        assert_eq!(par_chunks(values.clone(), 4, |chunk| chunk), values);
+    }
+
+    #[test]
+    fn single_thread_chunks() {
+        let values: Vec<usize> = (0..10_000).collect();
+
+        assert_eq!(
+            par_chunks(values.clone(), 1, |chunk| chunk
+                .into_iter()
+                .map(|x| x * 2)
+                .collect()),
+            values.iter().map(|x| x * 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn join_returns_both_sides() {
+        assert_eq!(par_join(4, |_| 1, |_| "a"), (1, "a"));
+        assert_eq!(par_join(1, |threads| threads, |threads| threads), (1, 1));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{EvalContext, EvalError, Expression};
+
+use std::any::Any;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match ``predicate``,
+/// combining them with ``mapper``, given the parameters bound at evaluation time.
+///
+/// The parameters are bound with ``eval_with`` or ``EvalContext::with_params``.
+/// Evaluating without binding parameters of type ``P`` fails.
+/// Since ``par_eval`` cannot bind parameters, plans containing a ``ParamJoin``
+/// do not implement ``ParallelExpression``.
+pub struct ParamJoin<L, R, Res, P, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R, &P) -> bool + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R, &P) -> Res + Send + Sync>,
+}
+
+impl<L, R, Res, P, EL, ER> ParamJoin<L, R, Res, P, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R, &P) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R, &P) -> Res + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<L, R, Res, P, EL, ER> Clone for ParamJoin<L, R, Res, P, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            left_expression: self.left_expression.clone(),
+            right_expression: self.right_expression.clone(),
+            predicate: self.predicate.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<L, R, Res, P, EL, ER> Expression<Res> for ParamJoin<L, R, Res, P, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+        let params = context.params::<P>("ParamJoin")?;
+
+        let mut result = Vec::new();
+
+        for row1 in &left_result {
+            context.check("ParamJoin", result.len())?;
+
+            for row2 in &right_result {
+                if (self.predicate)(row1, row2, params) {
+                    result.push((self.mapper)(row1, row2, params));
+                    context.check_rows("ParamJoin", result.len())?;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn bind_different_params() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[(1, 10), (2, 20)];
+
+        let join = ParamJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x, y, min: &i32| x.0 == y.0 && y.1 >= *min,
+            |x, y, _| (x.1, y.1),
+        );
+
+        assert_eq!(join.eval_with(&0), &[("a", 10), ("b", 20)]);
+        assert_eq!(join.eval_with(&15), &[("b", 20)]);
+    }
+}
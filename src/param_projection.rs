@@ -0,0 +1,86 @@
+use crate::{EvalContext, EvalError, Expression};
+
+use std::any::Any;
+use std::sync::Arc;
+
+/// Maps each tuple in ``expression`` using ``mapper`` given the parameters
+/// bound at evaluation time.
+///
+/// The parameters are bound with ``eval_with`` or ``EvalContext::with_params``.
+/// Evaluating without binding parameters of type ``P`` fails.
+/// Since ``par_eval`` cannot bind parameters, plans containing a ``ParamProjection``
+/// do not implement ``ParallelExpression``.
+pub struct ParamProjection<S, T, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub mapper: Arc<dyn Fn(&S, &P) -> T + Send + Sync>,
+}
+
+impl<S, T, P, E> ParamProjection<S, T, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, mapper: impl Fn(&S, &P) -> T + Send + Sync + 'static) -> Self {
+        Self {
+            expression,
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T, P, E> Clone for ParamProjection<S, T, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<S, T, P, E> Expression<T> for ParamProjection<S, T, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<T> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<T>, EvalError> {
+        let rows = self.expression.try_eval_with_context(context)?;
+        let params = context.params::<P>("ParamProjection")?;
+
+        Ok(rows.iter().map(|row| (self.mapper)(row, params)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn bind_different_params() {
+        let values = &[1, 2, 3];
+        let projection = ParamProjection::new(Terminal::new(values), |x, factor: &i32| x * factor);
+
+        assert_eq!(projection.eval_with(&2), &[2, 4, 6]);
+        assert_eq!(projection.eval_with(&10), &[10, 20, 30]);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{EvalContext, EvalError, Expression};
+
+use std::any::Any;
+use std::sync::Arc;
+
+/// Filters the tuples in ``expression`` that satisfy ``predicate`` given the
+/// parameters bound at evaluation time.
+///
+/// The parameters are bound with ``eval_with`` or ``EvalContext::with_params``.
+/// Evaluating without binding parameters of type ``P`` fails.
+/// Since ``par_eval`` cannot bind parameters, plans containing a ``ParamSelection``
+/// do not implement ``ParallelExpression``.
+///
+/// ```compile_fail
+/// use ra_evaluator::{ParallelExpression, ParamSelection, Terminal};
+///
+/// let plan = ParamSelection::new(Terminal::new(&[1, 2, 3]), |x, min: &i32| x >= min);
+///
+/// plan.par_eval(2);
+/// ```
+pub struct ParamSelection<S, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub predicate: Arc<dyn Fn(&S, &P) -> bool + Send + Sync>,
+}
+
+impl<S, P, E> ParamSelection<S, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, predicate: impl Fn(&S, &P) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            expression,
+            predicate: Arc::new(predicate),
+        }
+    }
+}
+
+impl<S, P, E> Clone for ParamSelection<S, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+impl<S, P, E> Expression<S> for ParamSelection<S, P, E>
+where
+    S: Clone + Eq + PartialEq,
+    P: Any + Send + Sync,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let rows = self.expression.try_eval_with_context(context)?;
+        let params = context.params::<P>("ParamSelection")?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|row| (self.predicate)(row, params))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MissingParams, Terminal};
+
+    #[test]
+    fn bind_different_params() {
+        let values = &[(1, "a"), (2, "b"), (3, "c")];
+        let selection = ParamSelection::new(Terminal::new(values), |x, id: &i32| x.0 == *id);
+
+        assert_eq!(selection.eval_with(&1), &[(1, "a")]);
+        assert_eq!(selection.eval_with(&3), &[(3, "c")]);
+    }
+
+    #[test]
+    fn missing_params() {
+        let values = &[(1, "a")];
+        let selection = ParamSelection::new(Terminal::new(values), |x, id: &i32| x.0 == *id);
+
+        let error = selection.try_eval().unwrap_err();
+
+        assert_eq!(error.operator, "ParamSelection");
+        assert!(error.source.downcast_ref::<MissingParams>().is_some());
+    }
+}
@@ -1,4 +1,9 @@
-use crate::Expression;
+use crate::parallel::par_chunks;
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
+
+use std::sync::Arc;
 
 /// Picks a subset of columns from each tuple according to ``mapper``.
 /// 
@@ -12,7 +17,7 @@ where
     E: Expression<S>,
 {
     pub expression: E,
-    pub mapper: fn(&S) -> T
+    pub mapper: Arc<dyn Fn(&S) -> T + Send + Sync>
 }
 
 impl<S, T, E> Projection<S, T, E>
@@ -21,10 +26,10 @@ where
     T: Clone + Eq + PartialEq,
     E: Expression<S>, 
 {
-    pub fn new(expression: E, mapper: fn(&S) -> T) -> Self {
+    pub fn new(expression: E, mapper: impl Fn(&S) -> T + Send + Sync + 'static) -> Self {
         Self {
             expression,
-            mapper
+            mapper: Arc::new(mapper)
         }
     }
 }
@@ -36,7 +41,72 @@ where
     E: Expression<S>,
 {
     fn eval(&self) -> Vec<T> {
-        self.expression.eval().iter().map(self.mapper).collect()
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<T>, EvalError> {
+        Ok(self.expression.try_eval_with_context(context)?.iter().map(&*self.mapper).collect())
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.expression.iter().map(move |row| (self.mapper)(&row)))
+    }
+
+    fn try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<T, EvalError>> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(
+            self.expression
+                .try_iter_with_context(context)
+                .map(move |row| row.map(|row| (self.mapper)(&row))),
+        )
+    }
+}
+
+impl<S, T, E> ParallelExpression<T> for Projection<S, T, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    T: Clone + Eq + PartialEq + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    /// Partitions the result of ``expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<T> {
+        par_chunks(self.expression.par_eval(threads), threads, |rows| {
+            rows.iter().map(&*self.mapper).collect()
+        })
+    }
+}
+
+impl<S, T, E> IncrementalExpression<T> for Projection<S, T, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    E: IncrementalExpression<S>,
+{
+    type State = E::State;
+
+    fn init(&self) -> E::State {
+        self.expression.init()
+    }
+
+    fn delta(&self, state: &mut E::State, changes: &Changes) -> Delta<T> {
+        let delta = self.expression.delta(state, changes);
+
+        Delta::new(
+            delta.inserted.iter().map(&*self.mapper).collect(),
+            delta.deleted.iter().map(&*self.mapper).collect(),
+        )
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.expression.apply(changes);
     }
 }
 
@@ -0,0 +1,256 @@
+/// Builds an ``Expression`` from relational algebra written in textbook notation.
+///
+/// The macro expands directly into nested ``Selection``, ``Projection``, ``Join``,
+/// ``CartesianProduct``, ``Union``, ``Intersection`` and ``Difference`` constructors,
+/// so the result is the same as building the expression by hand.
+///
+/// Relations are written as variables holding expressions, which are cloned so
+/// that the same relation can appear more than once, or as ``{ ... }`` blocks
+/// containing any expression. Operations are written as follows:
+///
+/// | Notation | Written as |
+/// |----------|------------|
+/// | σ<sub>p</sub>(R) | ``σ[p](R)`` or ``select[p](R)`` |
+/// | π<sub>f</sub>(R) | ``π[f](R)`` or ``project[f](R)`` |
+/// | R ⋈<sub>p</sub> S | ``R join[p, f] S``, where ``f`` combines matching tuples |
+/// | R × S | ``R product[f] S``, where ``f`` combines each pair of tuples |
+/// | R ∪ S | ``R union S`` |
+/// | R ∩ S | ``R intersect S`` |
+/// | R − S | ``R minus S`` |
+///
+/// Since ⋈, ×, ∪, ∩ and − are not valid Rust tokens, binary operations are written
+/// as words. They all have the same precedence and associate to the left, so
+/// parentheses must be used to group them otherwise.
+///
+/// Crates using σ and π may need ``#![allow(mixed_script_confusables)]``, since
+/// rustc warns about Greek identifiers that resemble Latin letters. The ``select``
+/// and ``project`` spellings avoid this.
+///
+/// ```rust
+/// use ra_evaluator::{ra, Expression, Terminal};
+///
+/// let r = Terminal::new(&[(1, "a"), (2, "b"), (3, "c")]);
+/// let s = Terminal::new(&[("b", 10), ("c", 20)]);
+///
+/// let query = ra!(
+///     π[|x| x.0 + x.1](
+///         σ[|x| x.0 > 1](r) join[|x, y| x.1 == y.0, |x, y| (x.0, y.1)] s
+///     )
+///     union { Terminal::new(&[0]) }
+/// );
+///
+/// assert_eq!(query.eval(), &[12, 23, 0]);
+/// ```
+///
+/// Malformed expressions are rejected with a ``compile_error!`` describing the
+/// expected notation. A unary operation must have its ``[...]``:
+///
+/// ```compile_fail
+/// # use ra_evaluator::{ra, Terminal};
+/// # let r = Terminal::new(&[1]);
+/// # let s = Terminal::new(&[2]);
+/// let query = ra!(r union select(s));
+/// ```
+///
+/// Only the binary operations listed above are known:
+///
+/// ```compile_fail
+/// # use ra_evaluator::{ra, Terminal};
+/// # let r = Terminal::new(&[1]);
+/// # let s = Terminal::new(&[2]);
+/// let query = ra!(r times s);
+/// ```
+///
+/// A join needs both a predicate and a mapper:
+///
+/// ```compile_fail
+/// # use ra_evaluator::{ra, Terminal};
+/// # let r = Terminal::new(&[1]);
+/// # let s = Terminal::new(&[2]);
+/// let query = ra!(r join[|x: &i32, y: &i32| x == y] s);
+/// ```
+#[macro_export]
+macro_rules! ra {
+    // Unary operations and relations.
+    (@term σ [$predicate:expr $(,)?] ($($input:tt)+)) => {
+        $crate::Selection::new($crate::ra!($($input)+), $predicate)
+    };
+    (@term π [$mapper:expr $(,)?] ($($input:tt)+)) => {
+        $crate::Projection::new($crate::ra!($($input)+), $mapper)
+    };
+    (@term select $($rest:tt)+) => {
+        $crate::ra!(@term σ $($rest)+)
+    };
+    (@term project $($rest:tt)+) => {
+        $crate::ra!(@term π $($rest)+)
+    };
+    (@term σ $($rest:tt)*) => {
+        compile_error!("ra!: selection must be written as `σ[predicate](input)`")
+    };
+    (@term π $($rest:tt)*) => {
+        compile_error!("ra!: projection must be written as `π[mapper](input)`")
+    };
+    (@term ($($inner:tt)+)) => {
+        $crate::ra!($($inner)+)
+    };
+    (@term { $expression:expr }) => {
+        $expression
+    };
+    (@term $relation:ident) => {
+        ::core::clone::Clone::clone(&$relation)
+    };
+    (@term $($other:tt)*) => {
+        compile_error!(concat!(
+            "ra!: expected a relation, `σ[...](...)`, `π[...](...)`, `(...)` or `{ ... }`, found `",
+            stringify!($($other)*),
+            "`"
+        ))
+    };
+
+    // Binary operations.
+    (@binary join [$predicate:expr, $mapper:expr $(,)?] ($left:expr) ($right:expr)) => {
+        $crate::Join::new($left, $right, $predicate, $mapper)
+    };
+    (@binary join [$($args:tt)*] ($left:expr) ($right:expr)) => {
+        compile_error!("ra!: join must be written as `left join[predicate, mapper] right`")
+    };
+    (@binary product [$mapper:expr $(,)?] ($left:expr) ($right:expr)) => {
+        $crate::CartesianProduct::new($left, $right, $mapper)
+    };
+    (@binary product [$($args:tt)*] ($left:expr) ($right:expr)) => {
+        compile_error!("ra!: cartesian product must be written as `left product[mapper] right`")
+    };
+    (@binary union [] ($left:expr) ($right:expr)) => {
+        $crate::Union::new($left, $right)
+    };
+    (@binary intersect [] ($left:expr) ($right:expr)) => {
+        $crate::Intersection::new($left, $right)
+    };
+    (@binary minus [] ($left:expr) ($right:expr)) => {
+        $crate::Difference::new($left, $right)
+    };
+    (@binary $operator:ident [$($args:tt)*] ($left:expr) ($right:expr)) => {
+        compile_error!(concat!(
+            "ra!: unknown binary operation `",
+            stringify!($operator [$($args)*]),
+            "`, expected `join[...]`, `product[...]`, `union`, `intersect` or `minus`"
+        ))
+    };
+
+    // Folds binary operations into ``$left`` from left to right, splitting off
+    // the operation and the term on its right each time.
+    (@fold ($left:expr)) => {
+        $left
+    };
+    (@fold ($left:expr) $operator:ident [$($args:tt)*]
+        $unary:ident [$($unary_args:tt)*] ($($input:tt)*) $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@binary $operator [$($args)*] ($left)
+            ($crate::ra!(@term $unary [$($unary_args)*] ($($input)*))))) $($rest)*)
+    };
+    (@fold ($left:expr) $operator:ident $([$($args:tt)*])? $unary:ident ($($input:tt)*)
+        $($rest:tt)*) => {
+        compile_error!(concat!(
+            "ra!: expected `[...]` after `",
+            stringify!($unary),
+            "`, as in `σ[predicate](input)` or `π[mapper](input)`"
+        ))
+    };
+    (@fold ($left:expr) $operator:ident [$($args:tt)*] $right:tt $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@binary $operator [$($args)*] ($left)
+            ($crate::ra!(@term $right)))) $($rest)*)
+    };
+    (@fold ($left:expr) $operator:ident
+        $unary:ident [$($unary_args:tt)*] ($($input:tt)*) $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@binary $operator [] ($left)
+            ($crate::ra!(@term $unary [$($unary_args)*] ($($input)*))))) $($rest)*)
+    };
+    (@fold ($left:expr) $operator:ident $right:tt $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@binary $operator [] ($left)
+            ($crate::ra!(@term $right)))) $($rest)*)
+    };
+    (@fold ($left:expr) $($other:tt)+) => {
+        compile_error!(concat!(
+            "ra!: expected a binary operation followed by a relation, found `",
+            stringify!($($other)+),
+            "`"
+        ))
+    };
+
+    () => {
+        compile_error!("ra!: expected a relational algebra expression")
+    };
+    ($unary:ident [$($args:tt)*] ($($input:tt)*) $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@term $unary [$($args)*] ($($input)*))) $($rest)*)
+    };
+    ($unary:ident ($($input:tt)*) $($rest:tt)*) => {
+        compile_error!(concat!(
+            "ra!: expected `[...]` after `",
+            stringify!($unary),
+            "`, as in `σ[predicate](input)` or `π[mapper](input)`"
+        ))
+    };
+    ($left:tt $($rest:tt)*) => {
+        $crate::ra!(@fold ($crate::ra!(@term $left)) $($rest)*)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn unary_operations() {
+        let r = Terminal::new(&[(1, "a"), (2, "b"), (3, "c")]);
+
+        assert_eq!(ra!(σ[|x| x.0 > 1](r)).eval(), &[(2, "b"), (3, "c")]);
+        assert_eq!(
+            ra!(project[|x| x.1](select[|x| x.0 < 3](r))).eval(),
+            &["a", "b"]
+        );
+    }
+
+    #[test]
+    fn binary_operations_associate_left() {
+        let r = Terminal::new(&[1, 2, 3]);
+        let s = Terminal::new(&[3, 4]);
+        let t = Terminal::new(&[3, 4, 5]);
+
+        assert_eq!(ra!(r union s intersect t).eval(), &[3, 4]);
+        assert_eq!(ra!(r union (s intersect t)).eval(), &[1, 2, 3, 3, 4]);
+        assert_eq!(ra!(t minus s union r).eval(), &[5, 1, 2, 3]);
+    }
+
+    #[test]
+    fn matches_constructors() {
+        let r = Terminal::new(&[(1, "a"), (2, "b")]);
+        let s = Terminal::new(&[("a", 10), ("b", 20)]);
+
+        let expected_result = Join::new(
+            Selection::new(r.clone(), |x| x.0 > 1),
+            CartesianProduct::new(s.clone(), Terminal::new(&[1, 2]), |y, z| (y.0, y.1 * z)),
+            |x, y| x.1 == y.0,
+            |x, y| (x.0, y.1),
+        )
+        .eval();
+
+        assert_eq!(
+            ra!(
+                σ[|x| x.0 > 1](r)
+                    join[|x, y| x.1 == y.0, |x, y| (x.0, y.1)]
+                    (s product[|y, z| (y.0, y.1 * z)] { Terminal::new(&[1, 2]) })
+            )
+            .eval(),
+            expected_result
+        );
+    }
+
+    #[test]
+    fn self_join() {
+        let edges = Terminal::new(&[(1, 2), (2, 3)]);
+
+        assert_eq!(
+            ra!(edges join[|x, y| x.1 == y.0, |x, y| (x.0, y.1)] edges).eval(),
+            &[(1, 3)]
+        );
+    }
+}
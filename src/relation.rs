@@ -0,0 +1,250 @@
+use crate::SortOrder;
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Describes the columns of a struct used as the tuples of a relation.
+///
+/// This is usually derived with ``#[derive(Relation)]``, which also generates a
+/// ``Column`` constant for each field, named after the field in upper case.
+/// Structs implementing ``Relation`` are ordinary tuples as far as ``Expression``
+/// is concerned, and can be converted to and from plain tuples of their fields.
+///
+/// ```rust
+/// use ra_evaluator::{ExpressionBuilder, Relation, Terminal};
+///
+/// #[derive(Clone, Debug, Eq, PartialEq, Relation)]
+/// struct Student {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// let students = Terminal::from_vec(vec![
+///     Student { id: 2, name: String::from("Bob") },
+///     Student { id: 1, name: String::from("Ann") },
+/// ]);
+///
+/// let query = ExpressionBuilder::new(students)
+///     .order_by(Student::ID)
+///     .project_columns(Student::NAME);
+///
+/// assert_eq!(Student::SCHEMA.to_string(), "Student(id: u32, name: String)");
+/// assert_eq!(query.eval(), &["Ann", "Bob"]);
+/// ```
+pub trait Relation: Clone + Eq + PartialEq {
+    const SCHEMA: Schema;
+
+    /// The plain tuple of the struct's fields, in declaration order.
+    type Tuple;
+
+    fn into_tuple(self) -> Self::Tuple;
+
+    fn from_tuple(tuple: Self::Tuple) -> Self;
+}
+
+/// The name and columns of a ``Relation``.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Schema {
+    pub name: &'static str,
+    pub columns: &'static [ColumnInfo],
+}
+
+/// The name and type of a column, with the type written as in the struct definition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ColumnInfo {
+    pub name: &'static str,
+    pub type_name: &'static str,
+}
+
+impl Schema {
+    pub fn column_names(&self) -> Vec<&'static str> {
+        self.columns.iter().map(|column| column.name).collect()
+    }
+
+    /// Looks up a column by ``name``.
+    pub fn column(&self, name: &str) -> Option<&'static ColumnInfo> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+/// Formats the schema as ``Name(column: Type, ...)``.
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+
+        for (index, column) in self.columns.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", column.name, column.type_name)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+/// A typed reference to the column ``name`` of the relation ``R``.
+///
+/// Columns can be used to project tuples with ``ExpressionBuilder::project_columns``,
+/// or as a ``SortOrder`` to sort them by the column in ascending order.
+pub struct Column<R, T> {
+    pub name: &'static str,
+    getter: fn(&R) -> &T,
+}
+
+impl<R, T> Column<R, T> {
+    pub const fn new(name: &'static str, getter: fn(&R) -> &T) -> Self {
+        Self { name, getter }
+    }
+
+    /// Returns the value of the column in ``row``.
+    pub fn get<'a>(&self, row: &'a R) -> &'a T {
+        (self.getter)(row)
+    }
+}
+
+impl<R, T> Clone for Column<R, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R, T> Copy for Column<R, T> {}
+
+impl<R, T> fmt::Debug for Column<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Column").field(&self.name).finish()
+    }
+}
+
+impl<R, T> SortOrder<R> for Column<R, T>
+where
+    T: Ord,
+{
+    fn compare(&self, a: &R, b: &R) -> Ordering {
+        self.get(a).cmp(self.get(b))
+    }
+}
+
+/// One or more ``Column``s of ``R`` to project, either a single column or a
+/// tuple of them.
+pub trait Columns<R>: Copy + Send + Sync + 'static {
+    type Output: Clone + Eq + PartialEq;
+
+    /// Returns the values of the columns in ``row``.
+    fn project(&self, row: &R) -> Self::Output;
+
+    fn names(&self) -> Vec<&'static str>;
+}
+
+impl<R, T> Columns<R> for Column<R, T>
+where
+    R: 'static,
+    T: Clone + Eq + PartialEq + 'static,
+{
+    type Output = T;
+
+    fn project(&self, row: &R) -> T {
+        self.get(row).clone()
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        vec![self.name]
+    }
+}
+
+macro_rules! impl_columns_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<R, $($name),+> Columns<R> for ($(Column<R, $name>,)+)
+        where
+            R: 'static,
+            $($name: Clone + Eq + PartialEq + 'static),+
+        {
+            type Output = ($($name,)+);
+
+            fn project(&self, row: &R) -> Self::Output {
+                ($(self.$index.get(row).clone(),)+)
+            }
+
+            fn names(&self) -> Vec<&'static str> {
+                vec![$(self.$index.name),+]
+            }
+        }
+    };
+}
+
+impl_columns_for_tuple!(A 0);
+impl_columns_for_tuple!(A 0, B 1);
+impl_columns_for_tuple!(A 0, B 1, C 2);
+impl_columns_for_tuple!(A 0, B 1, C 2, D 3);
+impl_columns_for_tuple!(A 0, B 1, C 2, D 3, F 4);
+impl_columns_for_tuple!(A 0, B 1, C 2, D 3, F 4, G 5);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Expression, Projection, Relation, Sort, Terminal};
+
+    #[derive(Clone, Debug, Eq, PartialEq, Relation)]
+    struct Employee {
+        id: u32,
+        name: &'static str,
+        manager: Option<u32>,
+    }
+
+    fn employees() -> Terminal<Employee> {
+        Terminal::from_vec(vec![
+            Employee {
+                id: 2,
+                name: "Bob",
+                manager: Some(1),
+            },
+            Employee {
+                id: 1,
+                name: "Ann",
+                manager: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn derived_schema() {
+        assert_eq!(Employee::SCHEMA.name, "Employee");
+        assert_eq!(Employee::SCHEMA.column_names(), &["id", "name", "manager"]);
+        assert_eq!(
+            Employee::SCHEMA.column("manager"),
+            Some(&ColumnInfo {
+                name: "manager",
+                type_name: "Option<u32>"
+            })
+        );
+        assert_eq!(
+            Employee::SCHEMA.to_string(),
+            "Employee(id: u32, name: &'static str, manager: Option<u32>)"
+        );
+    }
+
+    #[test]
+    fn project_and_sort_by_column() {
+        let columns = (Employee::NAME, Employee::MANAGER);
+        let sorted = Sort::new(employees(), Employee::ID);
+
+        assert_eq!(columns.names(), &["name", "manager"]);
+        assert_eq!(
+            Projection::new(sorted, move |row| columns.project(row)).eval(),
+            &[("Ann", None), ("Bob", Some(1))]
+        );
+    }
+
+    #[test]
+    fn tuple_conversion() {
+        let row = Employee {
+            id: 3,
+            name: "Cat",
+            manager: Some(2),
+        };
+
+        assert_eq!(row.clone().into_tuple(), (3, "Cat", Some(2)));
+        assert_eq!(Employee::from_tuple((3, "Cat", Some(2))), row);
+    }
+}
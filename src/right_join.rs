@@ -0,0 +1,167 @@
+use crate::parallel::{par_chunks, par_join};
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match some condition ``predicate``,
+/// as well as every tuple in ``right_expression`` that matches no tuple in ``left_expression``.
+///
+/// Unmatched right tuples are passed to the mapper with ``None`` in place of the left tuple.
+#[derive(Clone)]
+pub struct RightJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> bool + Send + Sync>,
+    pub mapper: Arc<dyn Fn(Option<&L>, &R) -> Res + Send + Sync>,
+}
+
+impl<L, R, Res, EL, ER> RightJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> bool + Send + Sync + 'static,
+        mapper: impl Fn(Option<&L>, &R) -> Res + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins the evaluated results of both sides.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Vec<Res> {
+        let mut result = Vec::new();
+
+        for row2 in right_result {
+            let mut matched = false;
+
+            for row1 in left_result {
+                if (self.predicate)(row1, row2) {
+                    matched = true;
+                    result.push((self.mapper)(Some(row1), row2));
+                }
+            }
+
+            if !matched {
+                result.push((self.mapper)(None, row2));
+            }
+        }
+
+        result
+    }
+}
+
+impl<L, R, Res, EL, ER> Expression<Res> for RightJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.combine(&self.left_expression.eval(), &self.right_expression.eval())
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        Ok(self.combine(
+            &self.left_expression.try_eval_with_context(context)?,
+            &self.right_expression.try_eval_with_context(context)?,
+        ))
+    }
+}
+
+impl<L, R, Res, EL, ER> ParallelExpression<Res> for RightJoin<L, R, Res, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+    /// Evaluates both sides concurrently, then partitions ``right_expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<Res> {
+        let (left_result, right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        par_chunks(right_result, threads, |rows| {
+            self.combine(&left_result, &rows)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn all_rows_matched() {
+        let values1 = &[(1, "a"), (2, "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            RightJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.1),
+            )
+            .eval(),
+            &[(Some("a"), "x"), (Some("b"), "y")]
+        );
+    }
+
+    #[test]
+    fn unmatched_right_rows() {
+        let values1 = &[(2, "b"), (4, "d")];
+        let values2 = &[(1, "x"), (2, "y"), (3, "z")];
+
+        assert_eq!(
+            RightJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.1),
+            )
+            .eval(),
+            &[(None, "x"), (Some("b"), "y"), (None, "z")]
+        );
+    }
+
+    #[test]
+    fn duplicate_matches() {
+        let values1 = &[(1, "a"), (1, "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            RightJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| x.0 == y.0,
+                |x, y| (x.map(|x| x.1), y.1),
+            )
+            .eval(),
+            &[(Some("a"), "x"), (Some("b"), "x"), (None, "y")]
+        );
+    }
+}
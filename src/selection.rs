@@ -1,4 +1,9 @@
-use crate::Expression;
+use crate::parallel::par_chunks;
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
+
+use std::sync::Arc;
 
 /// Filters the tuples in ``expression`` that satisfy ``predicate``.
 #[derive(Clone)]
@@ -8,7 +13,7 @@ where
     E: Expression<S>,
 {
     pub expression: E,
-    pub predicate: fn(&S) -> bool
+    pub predicate: Arc<dyn Fn(&S) -> bool + Send + Sync>
 }
 
 impl<S, E> Selection<S, E>
@@ -16,10 +21,10 @@ where
     S: Clone + Eq + PartialEq,
     E: Expression<S>, 
 {
-    pub fn new(expression: E, predicate: fn(&S) -> bool) -> Self {
+    pub fn new(expression: E, predicate: impl Fn(&S) -> bool + Send + Sync + 'static) -> Self {
         Self {
             expression,
-            predicate
+            predicate: Arc::new(predicate)
         }
     }
 }
@@ -30,7 +35,78 @@ where
     E: Expression<S>,
 {
     fn eval(&self) -> Vec<S> {
-        self.expression.eval().into_iter().filter(self.predicate).collect()
+        self.iter().collect()
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        Ok(self
+            .expression
+            .try_eval_with_context(context)?
+            .into_iter()
+            .filter(&*self.predicate)
+            .collect())
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(self.expression.iter().filter(&*self.predicate))
+    }
+
+    fn try_iter_with_context<'a>(
+        &'a self,
+        context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<S, EvalError>> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(
+            self.expression
+                .try_iter_with_context(context)
+                .filter(move |row| match row {
+                    Ok(row) => (self.predicate)(row),
+                    Err(_) => true,
+                }),
+        )
+    }
+}
+
+impl<S, E> ParallelExpression<S> for Selection<S, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    E: ParallelExpression<S>,
+{
+    /// Partitions the result of ``expression`` across threads.
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        par_chunks(self.expression.par_eval(threads), threads, |rows| {
+            rows.into_iter().filter(&*self.predicate).collect()
+        })
+    }
+}
+
+impl<S, E> IncrementalExpression<S> for Selection<S, E>
+where
+    S: Clone + Eq + PartialEq,
+    E: IncrementalExpression<S>,
+{
+    type State = E::State;
+
+    fn init(&self) -> E::State {
+        self.expression.init()
+    }
+
+    fn delta(&self, state: &mut E::State, changes: &Changes) -> Delta<S> {
+        let delta = self.expression.delta(state, changes);
+
+        Delta::new(
+            delta.inserted.into_iter().filter(&*self.predicate).collect(),
+            delta.deleted.into_iter().filter(&*self.predicate).collect(),
+        )
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.expression.apply(changes);
     }
 }
 
@@ -75,4 +151,15 @@ mod test {
             &[(2, "another string", 25)]
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn capture_runtime_value() {
+        let values = &[(1, "a"), (2, "b"), (3, "c")];
+        let threshold = values.len() as i32 - 1;
+
+        assert_eq!(
+            Selection::new(Terminal::new(values), move |x| x.0 >= threshold).eval(),
+            &[(2, "b"), (3, "c")]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+/// Determines how duplicate tuples are treated by an operator.
+///
+/// Relational algebra is defined over sets, but practical systems (and SQL)
+/// operate over bags (multisets) in which the same tuple may occur more than once.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Semantics {
+    /// Duplicates are eliminated from the result.
+    Set,
+    /// Duplicates are kept according to their multiplicities.
+    Bag,
+}
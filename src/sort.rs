@@ -0,0 +1,196 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Defines an ordering over tuples used by ``Sort``.
+///
+/// Implemented by comparator functions, by the ``Asc`` and ``Desc`` key extractors
+/// and by tuples of orderings, which compare lexicographically so that later
+/// orderings only break ties left by earlier ones.
+pub trait SortOrder<S>: Clone {
+    fn compare(&self, a: &S, b: &S) -> Ordering;
+}
+
+impl<S> SortOrder<S> for fn(&S, &S) -> Ordering {
+    fn compare(&self, a: &S, b: &S) -> Ordering {
+        self(a, b)
+    }
+}
+
+impl<S> SortOrder<S> for Arc<dyn Fn(&S, &S) -> Ordering + Send + Sync> {
+    fn compare(&self, a: &S, b: &S) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Orders tuples by the value of ``key`` from smallest to largest.
+#[derive(Clone)]
+pub struct Asc<S, K> {
+    pub key: Arc<dyn Fn(&S) -> K + Send + Sync>,
+}
+
+impl<S, K> Asc<S, K> {
+    pub fn new(key: impl Fn(&S) -> K + Send + Sync + 'static) -> Self {
+        Self { key: Arc::new(key) }
+    }
+}
+
+impl<S, K> SortOrder<S> for Asc<S, K>
+where
+    S: Clone,
+    K: Clone + Ord,
+{
+    fn compare(&self, a: &S, b: &S) -> Ordering {
+        (self.key)(a).cmp(&(self.key)(b))
+    }
+}
+
+/// Orders tuples by the value of ``key`` from largest to smallest.
+#[derive(Clone)]
+pub struct Desc<S, K> {
+    pub key: Arc<dyn Fn(&S) -> K + Send + Sync>,
+}
+
+impl<S, K> Desc<S, K> {
+    pub fn new(key: impl Fn(&S) -> K + Send + Sync + 'static) -> Self {
+        Self { key: Arc::new(key) }
+    }
+}
+
+impl<S, K> SortOrder<S> for Desc<S, K>
+where
+    S: Clone,
+    K: Clone + Ord,
+{
+    fn compare(&self, a: &S, b: &S) -> Ordering {
+        (self.key)(b).cmp(&(self.key)(a))
+    }
+}
+
+macro_rules! impl_sort_order_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<S, $($name),+> SortOrder<S> for ($($name,)+)
+        where
+            $($name: SortOrder<S>),+
+        {
+            fn compare(&self, a: &S, b: &S) -> Ordering {
+                Ordering::Equal $(.then_with(|| self.$index.compare(a, b)))+
+            }
+        }
+    };
+}
+
+impl_sort_order_for_tuple!(A 0);
+impl_sort_order_for_tuple!(A 0, B 1);
+impl_sort_order_for_tuple!(A 0, B 1, C 2);
+impl_sort_order_for_tuple!(A 0, B 1, C 2, D 3);
+impl_sort_order_for_tuple!(A 0, B 1, C 2, D 3, F 4);
+impl_sort_order_for_tuple!(A 0, B 1, C 2, D 3, F 4, G 5);
+
+/// Orders the tuples in ``expression`` according to ``order``.
+///
+/// The sort is stable, so tuples that compare equal keep their relative order.
+#[derive(Clone)]
+pub struct Sort<S, O, E>
+where
+    S: Clone + Eq + PartialEq,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub order: O,
+    phantom: PhantomData<S>,
+}
+
+impl<S, O, E> Sort<S, O, E>
+where
+    S: Clone + Eq + PartialEq,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, order: O) -> Self {
+        Self {
+            expression,
+            order,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, O, E> Expression<S> for Sort<S, O, E>
+where
+    S: Clone + Eq + PartialEq,
+    O: SortOrder<S>,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        let mut result = self.expression.eval();
+        result.sort_by(|a, b| self.order.compare(a, b));
+        result
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut result = self.expression.try_eval_with_context(context)?;
+        result.sort_by(|a, b| self.order.compare(a, b));
+        Ok(result)
+    }
+}
+
+impl<S, O, E> ParallelExpression<S> for Sort<S, O, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    O: SortOrder<S> + Sync,
+    E: ParallelExpression<S>,
+{
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let mut result = self.expression.par_eval(threads);
+        result.sort_by(|a, b| self.order.compare(a, b));
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn sort_by_comparator() {
+        let values = &[(2, 'b'), (3, 'c'), (1, 'a')];
+
+        assert_eq!(
+            Sort::new(
+                Terminal::new(values),
+                (|x, y| y.0.cmp(&x.0)) as fn(&(i32, char), &(i32, char)) -> Ordering
+            )
+            .eval(),
+            &[(3, 'c'), (2, 'b'), (1, 'a')]
+        );
+    }
+
+    #[test]
+    fn sort_by_multiple_keys() {
+        let values = &[(1, "a"), (2, "b"), (1, "c"), (2, "a")];
+
+        assert_eq!(
+            Sort::new(
+                Terminal::new(values),
+                (Asc::new(|x: &(i32, &str)| x.0), Desc::new(|x: &(i32, &str)| x.1))
+            )
+            .eval(),
+            &[(1, "c"), (1, "a"), (2, "b"), (2, "a")]
+        );
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let values = &[(1, "c"), (0, "b"), (1, "a"), (0, "d")];
+
+        assert_eq!(
+            Sort::new(Terminal::new(values), Asc::new(|x: &(i32, &str)| x.0)).eval(),
+            &[(0, "b"), (0, "d"), (1, "c"), (1, "a")]
+        );
+    }
+}
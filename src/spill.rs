@@ -0,0 +1,374 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Defines how tuples are written to and read back from temporary files by
+/// spilling operations such as ExternalSort.
+///
+/// Implemented for integers, ``bool``, ``char``, ``String``, ``Option``, ``Vec``
+/// and tuples of spillable types.
+pub trait Spill: Sized {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()>;
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self>;
+}
+
+macro_rules! impl_spill_for_number {
+    ($($number:ty),+) => {
+        $(
+            impl Spill for $number {
+                fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$number>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$number>::from_le_bytes(bytes))
+                }
+            }
+        )+
+    };
+}
+
+impl_spill_for_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Spill for bool {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u8).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        Ok(u8::read_from(reader)? != 0)
+    }
+}
+
+impl Spill for char {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        (*self as u32).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        char::from_u32(u32::read_from(reader)?)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid char"))
+    }
+}
+
+impl Spill for String {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        let mut bytes = vec![0; usize::read_from(reader)?];
+        reader.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl<T> Spill for Option<T>
+where
+    T: Spill,
+{
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        match self {
+            Some(value) => {
+                true.write_to(writer)?;
+                value.write_to(writer)
+            }
+            None => false.write_to(writer),
+        }
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        match bool::read_from(reader)? {
+            true => Ok(Some(T::read_from(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T> Spill for Vec<T>
+where
+    T: Spill,
+{
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        self.iter().try_for_each(|value| value.write_to(writer))
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+        (0..usize::read_from(reader)?)
+            .map(|_| T::read_from(reader))
+            .collect()
+    }
+}
+
+macro_rules! impl_spill_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name),+> Spill for ($($name,)+)
+        where
+            $($name: Spill),+
+        {
+            fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+                $(self.$index.write_to(writer)?;)+
+                Ok(())
+            }
+
+            fn read_from(reader: &mut dyn Read) -> io::Result<Self> {
+                Ok(($($name::read_from(reader)?,)+))
+            }
+        }
+    };
+}
+
+impl_spill_for_tuple!(A 0);
+impl_spill_for_tuple!(A 0, B 1);
+impl_spill_for_tuple!(A 0, B 1, C 2);
+impl_spill_for_tuple!(A 0, B 1, C 2, D 3);
+impl_spill_for_tuple!(A 0, B 1, C 2, D 3, F 4);
+impl_spill_for_tuple!(A 0, B 1, C 2, D 3, F 4, G 5);
+
+/// Configures the memory budget of spilling operations.
+///
+/// Once an operation holds more than ``budget`` rows in memory, it writes them
+/// to temporary files under ``dir``. Clones share the count of files written.
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    pub budget: usize,
+    pub dir: PathBuf,
+    pub partitions: usize,
+    spills: Arc<AtomicUsize>,
+}
+
+impl SpillConfig {
+    /// Spills to the system temporary directory once more than ``budget`` rows are held.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget: budget.max(1),
+            dir: std::env::temp_dir(),
+            partitions: 16,
+            spills: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Sets the number of partitions hash-based operations split their input into when spilling.
+    pub fn with_partitions(mut self, partitions: usize) -> Self {
+        self.partitions = partitions.max(1);
+        self
+    }
+
+    /// Returns the number of files written by operations using this configuration.
+    pub fn spill_count(&self) -> usize {
+        self.spills.load(Ordering::Relaxed)
+    }
+}
+
+/// A uniquely named directory of spill files, removed when dropped.
+pub(crate) struct SpillDir {
+    path: PathBuf,
+    config: SpillConfig,
+    files: usize,
+}
+
+impl SpillDir {
+    pub(crate) fn create(config: &SpillConfig) -> io::Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        let path = config.dir.join(format!(
+            "ra-evaluator-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+
+        Ok(Self {
+            path,
+            config: config.clone(),
+            files: 0,
+        })
+    }
+
+    /// Writes ``rows`` to a new file.
+    pub(crate) fn write<S>(&mut self, rows: impl IntoIterator<Item = S>) -> io::Result<SpillFile>
+    where
+        S: Spill,
+    {
+        let mut file = self.create_file()?;
+        for row in rows {
+            file.push(&row)?;
+        }
+        file.finish()
+    }
+
+    fn create_files(&mut self, count: usize) -> io::Result<Vec<SpillWriter>> {
+        (0..count).map(|_| self.create_file()).collect()
+    }
+
+    /// Creates a new empty file to be filled with ``SpillWriter::push``.
+    pub(crate) fn create_file(&mut self) -> io::Result<SpillWriter> {
+        let path = self.path.join(self.files.to_string());
+        self.files += 1;
+        self.config.spills.fetch_add(1, Ordering::Relaxed);
+
+        Ok(SpillWriter {
+            writer: BufWriter::new(File::create(&path)?),
+            file: SpillFile { path, rows: 0 },
+        })
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A file of spilled rows.
+#[derive(Clone)]
+pub(crate) struct SpillFile {
+    path: PathBuf,
+    rows: usize,
+}
+
+impl SpillFile {
+    /// Returns the number of rows in the file.
+    pub(crate) fn len(&self) -> usize {
+        self.rows
+    }
+
+    /// Reads the rows back in the order they were written.
+    pub(crate) fn read<S>(&self) -> io::Result<SpillReader<S>>
+    where
+        S: Spill,
+    {
+        Ok(SpillReader {
+            reader: BufReader::new(File::open(&self.path)?),
+            remaining: self.rows,
+            phantom: std::marker::PhantomData,
+        })
+    }
+}
+
+pub(crate) struct SpillWriter {
+    writer: BufWriter<File>,
+    file: SpillFile,
+}
+
+impl SpillWriter {
+    pub(crate) fn push<S>(&mut self, row: &S) -> io::Result<()>
+    where
+        S: Spill,
+    {
+        self.file.rows += 1;
+        row.write_to(&mut self.writer)
+    }
+
+    pub(crate) fn finish(mut self) -> io::Result<SpillFile> {
+        self.writer.flush()?;
+        Ok(self.file)
+    }
+}
+
+pub(crate) struct SpillReader<S> {
+    reader: BufReader<File>,
+    remaining: usize,
+    phantom: std::marker::PhantomData<S>,
+}
+
+impl<S> Iterator for SpillReader<S>
+where
+    S: Spill,
+{
+    type Item = io::Result<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(S::read_from(&mut self.reader))
+    }
+}
+
+/// Writes ``rows`` to one file per partition of ``dir``, choosing each row's
+/// partition by hashing its ``key`` together with ``seed``.
+///
+/// Partitioning a partition again with a different ``seed`` splits its rows
+/// between the new partitions, unless their keys are all equal.
+pub(crate) fn partition<S, K>(
+    dir: &mut SpillDir,
+    rows: impl Iterator<Item = io::Result<S>>,
+    seed: u64,
+    key: impl Fn(&S) -> K,
+) -> io::Result<Vec<SpillFile>>
+where
+    S: Spill,
+    K: Hash,
+{
+    let partitions = dir.config.partitions;
+    let mut files = dir.create_files(partitions)?;
+
+    for row in rows {
+        let row = row?;
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        key(&row).hash(&mut hasher);
+
+        files[(hasher.finish() % partitions as u64) as usize].push(&row)?;
+    }
+
+    files.into_iter().map(SpillWriter::finish).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let row = (
+            1i32,
+            String::from("test string"),
+            Some('x'),
+            vec![true, false],
+            None::<u64>,
+        );
+        let mut bytes = Vec::new();
+
+        row.write_to(&mut bytes).unwrap();
+
+        assert_eq!(Spill::read_from(&mut &bytes[..]).ok(), Some(row));
+    }
+
+    #[test]
+    fn spill_dir_removed_on_drop() {
+        let config = SpillConfig::new(1);
+        let mut dir = SpillDir::create(&config).unwrap();
+        let path = dir.path.clone();
+
+        let file = dir.write(vec![1, 2, 3]).unwrap();
+        assert_eq!(
+            file.read::<i32>()
+                .unwrap()
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap(),
+            &[1, 2, 3]
+        );
+        assert_eq!(config.spill_count(), 1);
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
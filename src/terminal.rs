@@ -1,15 +1,85 @@
-use crate::Expression;
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
 
 /// An identity expression to mark the end of an expression chain.
 /// 
 /// This is intended to be a basic and transparent wrapper of a relation
 /// in order to start expression building.
+///
+/// The rows are stored behind an ``Arc`` so cloning a ``Terminal`` (and hence
+/// any expression built on top of it) shares rather than copies the relation.
+/// Each ``Terminal`` also has an identity, shared by its clones, which ``Changes``
+/// use to refer to it. Changes applied to a ``Terminal`` are kept in an overlay
+/// alongside the shared rows rather than copying them.
 #[derive(Clone)]
 pub struct Terminal<S>
 where
     S: Clone + Eq + PartialEq,
 {
-    rows: Vec<S>,
+    id: u64,
+    rows: Arc<[S]>,
+    overlay: Option<Arc<Overlay<S>>>,
+}
+
+/// The rows inserted into and deleted from the shared rows of a ``Terminal``.
+///
+/// Positions count the rows of the ``Terminal`` followed by ``inserted``.
+#[derive(Clone)]
+struct Overlay<S> {
+    inserted: Vec<S>,
+    deleted: HashSet<usize>,
+    /// The positions of the rows that have not been deleted, by row.
+    positions: HashMap<S, Vec<usize>>,
+}
+
+impl<S> Overlay<S>
+where
+    S: Clone + Eq + PartialEq + Hash,
+{
+    fn new(rows: &[S]) -> Self {
+        let mut positions: HashMap<S, Vec<usize>> = HashMap::new();
+
+        for (position, row) in rows.iter().enumerate() {
+            positions.entry(row.clone()).or_default().push(position);
+        }
+
+        Self {
+            inserted: Vec::new(),
+            deleted: HashSet::new(),
+            positions,
+        }
+    }
+
+    /// Applies ``delta`` to the overlay of ``rows``, removing one occurrence of each
+    /// deleted row before appending the inserted rows.
+    fn apply(&mut self, rows: &[S], delta: &Delta<S>) {
+        for row in &delta.deleted {
+            if let Some(positions) = self.positions.get_mut(row) {
+                if let Some(position) = positions.pop() {
+                    self.deleted.insert(position);
+                }
+                if positions.is_empty() {
+                    self.positions.remove(row);
+                }
+            }
+        }
+
+        for row in &delta.inserted {
+            let position = rows.len() + self.inserted.len();
+
+            self.positions.entry(row.clone()).or_default().push(position);
+            self.inserted.push(row.clone());
+        }
+    }
 }
 
 impl<S> Terminal<S> 
@@ -17,18 +87,219 @@ where
     S: Clone + Eq + PartialEq,
 {
     pub fn new(rows: &[S]) -> Self {
+        Self::from_shared(rows.into())
+    }
+
+    /// Takes ownership of ``rows`` rather than copying them from a slice.
+    pub fn from_vec(rows: Vec<S>) -> Self {
+        Self::from_shared(rows.into())
+    }
+
+    /// Wraps an already shared relation without copying it.
+    pub fn from_shared(rows: Arc<[S]>) -> Self {
         Self {
-            rows: rows.to_vec(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            rows,
+            overlay: None,
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the rows of the relation, which are only copied if changes have
+    /// been applied to it.
+    pub fn rows(&self) -> Cow<'_, [S]> {
+        match self.overlay {
+            Some(_) => Cow::Owned(self.eval()),
+            None => Cow::Borrowed(&self.rows),
         }
     }
 }
 
+impl<S> FromIterator<S> for Terminal<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn from_iter<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+    {
+        Self::from_vec(rows.into_iter().collect())
+    }
+}
+
+/// Converts a collection into a ``Terminal`` relation.
+///
+/// Maps become relations of ``(key, value)`` tuples. The order of the rows is
+/// the iteration order of the collection, so it is unspecified for hashed collections.
+pub trait IntoRelation<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<S>;
+}
+
+impl<S> IntoRelation<S> for &[S]
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<S> {
+        Terminal::new(self)
+    }
+}
+
+impl<S> IntoRelation<S> for Vec<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<S> {
+        Terminal::from_vec(self)
+    }
+}
+
+impl<S> IntoRelation<S> for HashSet<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<S> {
+        self.into_iter().collect()
+    }
+}
+
+impl<S> IntoRelation<S> for BTreeSet<S>
+where
+    S: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<S> {
+        self.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoRelation<(K, V)> for HashMap<K, V>
+where
+    K: Clone + Eq + PartialEq,
+    V: Clone + Eq + PartialEq,
+{
+    fn into_relation(self) -> Terminal<(K, V)> {
+        self.into_iter().collect()
+    }
+}
+
 impl<S> Expression<S> for Terminal<S>
 where 
     S: Clone + Eq + PartialEq,
 {
     fn eval(&self) -> Vec<S> {
-        self.rows.clone()
+        match self.overlay {
+            Some(_) => self.iter().collect(),
+            None => self.rows.to_vec(),
+        }
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        match &self.overlay {
+            Some(overlay) => Box::new(
+                self.rows
+                    .iter()
+                    .chain(&overlay.inserted)
+                    .enumerate()
+                    .filter(|(position, _)| !overlay.deleted.contains(position))
+                    .map(|(_, row)| row.clone()),
+            ),
+            None => Box::new(self.rows.iter().cloned()),
+        }
+    }
+
+    fn try_iter_with_context<'a>(
+        &'a self,
+        _context: &'a EvalContext,
+    ) -> Box<dyn Iterator<Item = Result<S, EvalError>> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(self.iter().map(Ok))
+    }
+}
+
+impl<S> ParallelExpression<S> for Terminal<S>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+{
+}
+
+impl<S> IncrementalExpression<S> for Terminal<S>
+where
+    S: Clone + Eq + PartialEq + Hash + 'static,
+{
+    type State = ();
+
+    fn init(&self) {}
+
+    /// Drops the deletes of rows that are not present, comparing them with the
+    /// overlay, or with the rows if no changes have been applied yet.
+    fn delta(&self, _: &mut (), changes: &Changes) -> Delta<S> {
+        let Some(delta) = changes.get(self) else {
+            return Delta::empty();
+        };
+
+        let mut present: HashMap<&S, usize> = delta.deleted.iter().map(|row| (row, 0)).collect();
+
+        match &self.overlay {
+            Some(overlay) => {
+                for (row, count) in present.iter_mut() {
+                    *count = overlay.positions.get(*row).map_or(0, Vec::len);
+                }
+            }
+            None => {
+                for row in self.rows.iter() {
+                    if let Some(count) = present.get_mut(row) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        let deleted = delta
+            .deleted
+            .iter()
+            .filter(|row| match present.get_mut(row) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect();
+
+        Delta::new(delta.inserted.clone(), deleted)
+    }
+
+    /// Records the change in the overlay, which is only copied if it is shared with
+    /// a clone of the ``Terminal``. The rows are copied once more than half of them
+    /// have been deleted, so that the overlay does not keep growing.
+    fn apply(&mut self, changes: &Changes) {
+        let Some(delta) = changes.get(self) else {
+            return;
+        };
+
+        let rows = &self.rows;
+        let overlay = self
+            .overlay
+            .get_or_insert_with(|| Arc::new(Overlay::new(rows)));
+        Arc::make_mut(overlay).apply(rows, delta);
+
+        let deleted = overlay.deleted.len();
+
+        if deleted > rows.len() + overlay.inserted.len() - deleted {
+            self.rows = self.iter().collect();
+            self.overlay = None;
+        }
     }
 }
 
@@ -57,4 +328,60 @@ mod test {
 
         assert_eq!((Terminal::new(values)).eval(), values);
     }
+
+    #[test]
+    fn iterate_values() {
+        let values = &[1, 2, 3];
+
+        assert_eq!(Terminal::new(values).iter().collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn from_collections() {
+        let rows = vec![3, 1, 2];
+
+        assert_eq!(Terminal::from_vec(rows.clone()).eval(), &[3, 1, 2]);
+        assert_eq!(rows.iter().map(|x| x * 2).collect::<Terminal<_>>().eval(), &[6, 2, 4]);
+        assert_eq!(rows[..2].into_relation().eval(), &[3, 1]);
+        assert_eq!(
+            rows.iter().copied().collect::<BTreeSet<_>>().into_relation().eval(),
+            &[1, 2, 3]
+        );
+
+        let mut pairs = HashMap::from([(1, "a"), (2, "b")]).into_relation().eval();
+        pairs.sort();
+        assert_eq!(pairs, &[(1, "a"), (2, "b")]);
+    }
+
+    #[test]
+    fn clones_share_rows() {
+        let rows: Arc<[i32]> = Arc::from(vec![1, 2, 3]);
+        let terminal = Terminal::from_shared(rows.clone());
+        let cloned = terminal.clone();
+
+        assert!(std::ptr::eq(&*terminal.rows(), &rows[..]));
+        assert!(std::ptr::eq(&*cloned.rows(), &rows[..]));
+        assert_eq!(cloned.eval(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn changes_kept_in_overlay() {
+        let rows: Arc<[i32]> = Arc::from(vec![1, 2, 2, 3]);
+        let original = Terminal::from_shared(rows.clone());
+        let mut terminal = original.clone();
+        let mut changes = Changes::new();
+
+        changes.insert(&terminal, Delta::new(vec![4, 2], vec![2, 3, 5]));
+        terminal.apply(&changes);
+
+        assert!(std::ptr::eq(&*terminal.rows, &rows[..]));
+        assert_eq!(terminal.eval(), &[1, 2, 4, 2]);
+        assert_eq!(original.eval(), &[1, 2, 2, 3]);
+
+        changes.insert(&terminal, Delta::new(vec![], vec![2, 2, 4]));
+        terminal.apply(&changes);
+
+        assert_eq!(terminal.rows(), &[1][..]);
+        assert!(terminal.overlay.is_none());
+    }
 }
@@ -0,0 +1,175 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Finds the tuples ``q`` such that ``(q, d)`` is in ``dividend_expression`` for
+/// every tuple ``d`` in ``divisor_expression``, splitting each dividend tuple with
+/// the fallible ``quotient_mapper`` and ``divisor_mapper``.
+///
+/// This is the fallible counterpart of ``Division`` and produces its quotients in the
+/// same order. Evaluation with ``try_eval`` stops at the first error returned by either
+/// function. Calling ``eval`` panics if either fails.
+pub struct TryDivision<S, D, Q, Err, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    Err: Error + Send + Sync + 'static,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    pub dividend_expression: E1,
+    pub divisor_expression: E2,
+    pub quotient_mapper: Arc<dyn Fn(&S) -> Result<Q, Err> + Send + Sync>,
+    pub divisor_mapper: Arc<dyn Fn(&S) -> Result<D, Err> + Send + Sync>,
+}
+
+impl<S, D, Q, Err, E1, E2> TryDivision<S, D, Q, Err, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    Err: Error + Send + Sync + 'static,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    pub fn new(
+        dividend_expression: E1,
+        divisor_expression: E2,
+        quotient_mapper: impl Fn(&S) -> Result<Q, Err> + Send + Sync + 'static,
+        divisor_mapper: impl Fn(&S) -> Result<D, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            dividend_expression,
+            divisor_expression,
+            quotient_mapper: Arc::new(quotient_mapper),
+            divisor_mapper: Arc::new(divisor_mapper),
+        }
+    }
+
+    /// Divides the evaluated dividend by the evaluated divisor.
+    fn combine(&self, dividend_result: &[S], divisor_result: Vec<D>) -> Result<Vec<Q>, Err> {
+        let divisor: HashSet<D> = divisor_result.into_iter().collect();
+
+        let mut quotients: Vec<Q> = Vec::new();
+        let mut matched: HashMap<Q, HashSet<D>> = HashMap::new();
+
+        for row in dividend_result {
+            let quotient = (self.quotient_mapper)(row)?;
+            let remainder = (self.divisor_mapper)(row)?;
+
+            let divisors = matched.entry(quotient.clone()).or_insert_with(|| {
+                quotients.push(quotient);
+                HashSet::new()
+            });
+
+            if divisor.contains(&remainder) {
+                divisors.insert(remainder);
+            }
+        }
+
+        Ok(quotients
+            .into_iter()
+            .filter(|quotient| matched[quotient].len() == divisor.len())
+            .collect())
+    }
+}
+
+impl<S, D, Q, Err, E1, E2> Clone for TryDivision<S, D, Q, Err, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    Err: Error + Send + Sync + 'static,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            dividend_expression: self.dividend_expression.clone(),
+            divisor_expression: self.divisor_expression.clone(),
+            quotient_mapper: self.quotient_mapper.clone(),
+            divisor_mapper: self.divisor_mapper.clone(),
+        }
+    }
+}
+
+impl<S, D, Q, Err, E1, E2> Expression<Q> for TryDivision<S, D, Q, Err, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    D: Clone + Eq + PartialEq + Hash,
+    Q: Clone + Eq + PartialEq + Hash,
+    Err: Error + Send + Sync + 'static,
+    E1: Expression<S>,
+    E2: Expression<D>,
+{
+    fn eval(&self) -> Vec<Q> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Q>, EvalError> {
+        let dividend_result = self.dividend_expression.try_eval_with_context(context)?;
+        let divisor_result = self.divisor_expression.try_eval_with_context(context)?;
+
+        self.combine(&dividend_result, divisor_result)
+            .map_err(|error| EvalError::new("TryDivision", error))
+    }
+}
+
+impl<S, D, Q, Err, E1, E2> ParallelExpression<Q> for TryDivision<S, D, Q, Err, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    D: Clone + Eq + PartialEq + Hash + Send + Sync,
+    Q: Clone + Eq + PartialEq + Hash + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    E1: ParallelExpression<S>,
+    E2: ParallelExpression<D>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn suppliers_of_all_parsed_parts() {
+        let supplies = &[("s1", "1"), ("s1", "2"), ("s2", "1")];
+        let parts = &[1, 2];
+
+        assert_eq!(
+            TryDivision::new(
+                Terminal::new(supplies),
+                Terminal::new(parts),
+                |x| Ok(x.0),
+                |x| x.1.parse::<i32>(),
+            )
+            .try_eval()
+            .unwrap(),
+            &["s1"]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let supplies = &[("s1", "1"), ("s1", "oops")];
+        let parts = &[1, 2];
+
+        let error = TryDivision::new(
+            Terminal::new(supplies),
+            Terminal::new(parts),
+            |x| Ok(x.0),
+            |x| x.1.parse::<i32>(),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TryDivision");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match the fallible
+/// ``predicate``, as well as every tuple on either side that matches no tuple on the
+/// other, combining them with the fallible ``mapper``.
+///
+/// This is the fallible counterpart of ``FullJoin`` and produces its tuples in the same
+/// order. Evaluation with ``try_eval`` stops at the first error returned by either
+/// function. Calling ``eval`` panics if either fails.
+pub struct TryFullJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> Result<bool, Err> + Send + Sync>,
+    pub mapper: Arc<dyn Fn(Option<&L>, Option<&R>) -> Result<Res, Err> + Send + Sync>,
+}
+
+impl<L, R, Res, Err, EL, ER> TryFullJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(Option<&L>, Option<&R>) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins the evaluated results of both sides.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Result<Vec<Res>, Err> {
+        let mut result = Vec::new();
+        let mut right_matched = vec![false; right_result.len()];
+
+        for row1 in left_result {
+            let mut matched = false;
+
+            for (row2, row2_matched) in right_result.iter().zip(right_matched.iter_mut()) {
+                if (self.predicate)(row1, row2)? {
+                    matched = true;
+                    *row2_matched = true;
+                    result.push((self.mapper)(Some(row1), Some(row2))?);
+                }
+            }
+
+            if !matched {
+                result.push((self.mapper)(Some(row1), None)?);
+            }
+        }
+
+        for (row2, row2_matched) in right_result.iter().zip(right_matched) {
+            if !row2_matched {
+                result.push((self.mapper)(None, Some(row2))?);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> Clone for TryFullJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            left_expression: self.left_expression.clone(),
+            right_expression: self.right_expression.clone(),
+            predicate: self.predicate.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> Expression<Res> for TryFullJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+
+        self.combine(&left_result, &right_result)
+            .map_err(|error| EvalError::new("TryFullJoin", error))
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> ParallelExpression<Res> for TryFullJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn unmatched_rows_on_both_sides() {
+        let values1 = &[("1", "a"), ("2", "b")];
+        let values2 = &[(2, "y"), (4, "z")];
+
+        assert_eq!(
+            TryFullJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| Ok::<_, ParseIntError>(x.0.parse::<i32>()? == y.0),
+                |x, y| Ok((x.map(|x| x.1), y.map(|y| y.1))),
+            )
+            .try_eval()
+            .unwrap(),
+            &[(Some("a"), None), (Some("b"), Some("y")), (None, Some("z"))]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values1 = &[("oops", "a")];
+        let values2 = &[(2, "y")];
+
+        let error = TryFullJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x, y| Ok::<_, ParseIntError>(x.0.parse::<i32>()? == y.0),
+            |x, y| Ok((x.map(|x| x.1), y.map(|y| y.1))),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TryFullJoin");
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Aggregator, EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Partitions the tuples in ``expression`` into groups sharing the same key, as
+/// produced by the fallible ``key``, and reduces each group using ``aggregator``.
+///
+/// This is the fallible counterpart of ``GroupBy`` and produces its groups in the same
+/// order. Evaluation with ``try_eval`` stops at the first error returned by ``key``.
+/// Calling ``eval`` panics if it fails.
+pub struct TryGroupBy<S, K, A, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub key: Arc<dyn Fn(&S) -> Result<K, Err> + Send + Sync>,
+    pub aggregator: A,
+}
+
+impl<S, K, A, Err, E> TryGroupBy<S, K, A, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub fn new(
+        expression: E,
+        key: impl Fn(&S) -> Result<K, Err> + Send + Sync + 'static,
+        aggregator: A,
+    ) -> Self {
+        Self {
+            expression,
+            key: Arc::new(key),
+            aggregator,
+        }
+    }
+
+    /// Groups and aggregates the evaluated result of ``expression``.
+    fn aggregate(&self, rows: &[S]) -> Result<Vec<(K, A::Output)>, Err> {
+        let mut indices: HashMap<K, usize> = HashMap::new();
+        let mut groups: Vec<(K, A::State)> = Vec::new();
+
+        for row in rows {
+            let key = (self.key)(row)?;
+            let index = *indices.entry(key.clone()).or_insert_with(|| {
+                groups.push((key, self.aggregator.init()));
+                groups.len() - 1
+            });
+
+            self.aggregator.accumulate(&mut groups[index].1, row);
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(key, state)| (key, self.aggregator.finish(state)))
+            .collect())
+    }
+}
+
+impl<S, K, A, Err, E> Clone for TryGroupBy<S, K, A, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            key: self.key.clone(),
+            aggregator: self.aggregator.clone(),
+        }
+    }
+}
+
+impl<S, K, A, Err, E> Expression<(K, A::Output)> for TryGroupBy<S, K, A, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Clone + Eq + PartialEq + Hash,
+    A: Aggregator<S>,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<(K, A::Output)> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(
+        &self,
+        context: &EvalContext,
+    ) -> Result<Vec<(K, A::Output)>, EvalError> {
+        self.aggregate(&self.expression.try_eval_with_context(context)?)
+            .map_err(|error| EvalError::new("TryGroupBy", error))
+    }
+}
+
+impl<S, K, A, Err, E> ParallelExpression<(K, A::Output)> for TryGroupBy<S, K, A, Err, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    K: Clone + Eq + PartialEq + Hash + Send + Sync,
+    A: Aggregator<S> + Sync,
+    A::Output: Send,
+    Err: Error + Send + Sync + 'static,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Count, Terminal};
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn count_parsed_groups() {
+        let values = &["1", "2", "01"];
+
+        assert_eq!(
+            TryGroupBy::new(Terminal::new(values), |x| x.parse::<i32>(), Count)
+                .try_eval()
+                .unwrap(),
+            &[(1, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values = &["1", "oops"];
+
+        let error = TryGroupBy::new(Terminal::new(values), |x| x.parse::<i32>(), Count)
+            .try_eval()
+            .unwrap_err();
+
+        assert_eq!(error.operator, "TryGroupBy");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
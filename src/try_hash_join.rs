@@ -0,0 +1,200 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs whose keys are equal,
+/// extracting the keys and combining the pairs with fallible functions.
+///
+/// This is the fallible counterpart of ``HashJoin`` and likewise builds a hash table
+/// over the smaller of the two inputs. Evaluation with ``try_eval`` stops at the first
+/// error returned by any of the functions. Calling ``eval`` panics if one fails.
+pub struct TryHashJoin<L, R, K, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub left_key: Arc<dyn Fn(&L) -> Result<K, Err> + Send + Sync>,
+    pub right_key: Arc<dyn Fn(&R) -> Result<K, Err> + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R) -> Result<Res, Err> + Send + Sync>,
+}
+
+impl<L, R, K, Res, Err, EL, ER> TryHashJoin<L, R, K, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        left_key: impl Fn(&L) -> Result<K, Err> + Send + Sync + 'static,
+        right_key: impl Fn(&R) -> Result<K, Err> + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            left_key: Arc::new(left_key),
+            right_key: Arc::new(right_key),
+            mapper: Arc::new(mapper),
+        }
+    }
+
+    /// Joins the evaluated results of both sides, building the hash table over the smaller one.
+    fn combine(&self, left_result: &[L], right_result: &[R]) -> Result<Vec<Res>, Err> {
+        let mut result = Vec::new();
+
+        if left_result.len() < right_result.len() {
+            let table = build_table(left_result, &*self.left_key)?;
+
+            for row2 in right_result {
+                for row1 in table.get(&(self.right_key)(row2)?).into_iter().flatten() {
+                    result.push((self.mapper)(row1, row2)?);
+                }
+            }
+        } else {
+            let table = build_table(right_result, &*self.right_key)?;
+
+            for row1 in left_result {
+                for row2 in table.get(&(self.left_key)(row1)?).into_iter().flatten() {
+                    result.push((self.mapper)(row1, row2)?);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Groups references to ``rows`` by the key produced by the fallible ``key``.
+fn build_table<'a, T, K, Err>(
+    rows: &'a [T],
+    key: &dyn Fn(&T) -> Result<K, Err>,
+) -> Result<HashMap<K, Vec<&'a T>>, Err>
+where
+    K: Eq + Hash,
+{
+    let mut table: HashMap<K, Vec<&T>> = HashMap::new();
+
+    for row in rows {
+        table.entry(key(row)?).or_default().push(row);
+    }
+
+    Ok(table)
+}
+
+impl<L, R, K, Res, Err, EL, ER> Clone for TryHashJoin<L, R, K, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            left_expression: self.left_expression.clone(),
+            right_expression: self.right_expression.clone(),
+            left_key: self.left_key.clone(),
+            right_key: self.right_key.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<L, R, K, Res, Err, EL, ER> Expression<Res> for TryHashJoin<L, R, K, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+
+        self.combine(&left_result, &right_result)
+            .map_err(|error| EvalError::new("TryHashJoin", error))
+    }
+}
+
+impl<L, R, K, Res, Err, EL, ER> ParallelExpression<Res> for TryHashJoin<L, R, K, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    K: Eq + Hash,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn join_on_parsed_key() {
+        let values1 = &[("1", "a"), ("2", "b"), ("3", "c")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            TryHashJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x| x.0.parse::<i32>(),
+                |y| Ok(y.0),
+                |x, y| Ok((x.1, y.1)),
+            )
+            .try_eval()
+            .unwrap(),
+            &[("a", "x"), ("b", "y")]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values1 = &[("1", "a")];
+        let values2 = &[("1", "x"), ("oops", "y")];
+
+        let error = TryHashJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x| x.0.parse::<i32>(),
+            |y| y.0.parse::<i32>(),
+            |x, y| Ok((x.1, y.1)),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TryHashJoin");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// Keeps all ``(left_expression, right_expression)`` pairs that match the fallible
+/// ``predicate``, combining them with the fallible ``mapper``.
+///
+/// Evaluation with ``try_eval`` stops at the first error returned by either function.
+/// Calling ``eval`` panics if either function fails.
+pub struct TryJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> Result<bool, Err> + Send + Sync>,
+    pub mapper: Arc<dyn Fn(&L, &R) -> Result<Res, Err> + Send + Sync>,
+}
+
+impl<L, R, Res, Err, EL, ER> TryJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> Result<bool, Err> + Send + Sync + 'static,
+        mapper: impl Fn(&L, &R) -> Result<Res, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> Clone for TryJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            left_expression: self.left_expression.clone(),
+            right_expression: self.right_expression.clone(),
+            predicate: self.predicate.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> Expression<Res> for TryJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Res: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<Res> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<Res>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+
+        let to_eval_error = |error| EvalError::new("TryJoin", error);
+        let mut result = Vec::new();
+
+        for row1 in &left_result {
+            for row2 in &right_result {
+                if (self.predicate)(row1, row2).map_err(to_eval_error)? {
+                    result.push((self.mapper)(row1, row2).map_err(to_eval_error)?);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<L, R, Res, Err, EL, ER> ParallelExpression<Res> for TryJoin<L, R, Res, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Res: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn join_on_parsed_key() {
+        let values1 = &[("1", "a"), ("2", "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        assert_eq!(
+            TryJoin::new(
+                Terminal::new(values1),
+                Terminal::new(values2),
+                |x, y| Ok::<_, ParseIntError>(x.0.parse::<i32>()? == y.0),
+                |x, y| Ok((x.1, y.1)),
+            )
+            .try_eval()
+            .unwrap(),
+            &[("a", "x"), ("b", "y")]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values1 = &[("1", "a"), ("oops", "b")];
+        let values2 = &[(1, "x"), (2, "y")];
+
+        let error = TryJoin::new(
+            Terminal::new(values1),
+            Terminal::new(values2),
+            |x, y| Ok::<_, ParseIntError>(x.0.parse::<i32>()? == y.0),
+            |x, y| Ok((x.1, y.1)),
+        )
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TryJoin");
+    }
+}
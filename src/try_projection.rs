@@ -0,0 +1,113 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// Maps each tuple in ``expression`` using the fallible ``mapper``.
+///
+/// Evaluation with ``try_eval`` stops at the first error returned by ``mapper``.
+/// Calling ``eval`` panics if ``mapper`` fails.
+pub struct TryProjection<S, T, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub mapper: Arc<dyn Fn(&S) -> Result<T, Err> + Send + Sync>,
+}
+
+impl<S, T, Err, E> TryProjection<S, T, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub fn new(
+        expression: E,
+        mapper: impl Fn(&S) -> Result<T, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            expression,
+            mapper: Arc::new(mapper),
+        }
+    }
+}
+
+impl<S, T, Err, E> Clone for TryProjection<S, T, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<S, T, Err, E> Expression<T> for TryProjection<S, T, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    T: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<T> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<T>, EvalError> {
+        self.expression
+            .try_eval_with_context(context)?
+            .iter()
+            .map(|row| (self.mapper)(row).map_err(|error| EvalError::new("TryProjection", error)))
+            .collect()
+    }
+}
+
+impl<S, T, Err, E> ParallelExpression<T> for TryProjection<S, T, Err, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    T: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn map_all_rows() {
+        let values = &["1", "2", "3"];
+
+        assert_eq!(
+            TryProjection::new(Terminal::new(values), |x| x.parse::<i32>())
+                .try_eval()
+                .unwrap(),
+            &[1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values = &["1", "x", "3"];
+
+        let error = TryProjection::new(Terminal::new(values), |x| x.parse::<i32>())
+            .try_eval()
+            .unwrap_err();
+
+        assert_eq!(error.operator, "TryProjection");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// Filters the tuples in ``expression`` that satisfy the fallible ``predicate``.
+///
+/// Evaluation with ``try_eval`` stops at the first error returned by ``predicate``.
+/// Calling ``eval`` panics if ``predicate`` fails.
+pub struct TrySelection<S, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub predicate: Arc<dyn Fn(&S) -> Result<bool, Err> + Send + Sync>,
+}
+
+impl<S, Err, E> TrySelection<S, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub fn new(
+        expression: E,
+        predicate: impl Fn(&S) -> Result<bool, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            expression,
+            predicate: Arc::new(predicate),
+        }
+    }
+}
+
+impl<S, Err, E> Clone for TrySelection<S, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            predicate: self.predicate.clone(),
+        }
+    }
+}
+
+impl<S, Err, E> Expression<S> for TrySelection<S, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut result = Vec::new();
+
+        for row in self.expression.try_eval_with_context(context)? {
+            if (self.predicate)(&row).map_err(|error| EvalError::new("TrySelection", error))? {
+                result.push(row);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<S, Err, E> ParallelExpression<S> for TrySelection<S, Err, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn keep_some_rows() {
+        let values = &["1", "2", "3"];
+
+        assert_eq!(
+            TrySelection::new(Terminal::new(values), |x| Ok::<_, ParseIntError>(
+                x.parse::<i32>()? > 1
+            ))
+            .try_eval()
+            .unwrap(),
+            &["2", "3"]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values = &["1", "a", "b"];
+
+        let error = TrySelection::new(Terminal::new(values), |x| {
+            Ok::<_, ParseIntError>(x.parse::<i32>()? > 1)
+        })
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TrySelection");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "TrySelection failed")]
+    fn eval_panics_on_error() {
+        let values = &["a"];
+
+        TrySelection::new(Terminal::new(values), |x| {
+            Ok::<_, ParseIntError>(x.parse::<i32>()? > 1)
+        })
+        .eval();
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Keeps the tuples in ``left_expression`` that match at least one tuple in
+/// ``right_expression`` according to the fallible ``predicate``.
+///
+/// This is the fallible counterpart of ``SemiJoin``. Evaluation with ``try_eval`` stops
+/// at the first error returned by ``predicate``. Calling ``eval`` panics if it fails.
+pub struct TrySemiJoin<L, R, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub left_expression: EL,
+    pub right_expression: ER,
+    pub predicate: Arc<dyn Fn(&L, &R) -> Result<bool, Err> + Send + Sync>,
+    phantom: PhantomData<R>,
+}
+
+impl<L, R, Err, EL, ER> TrySemiJoin<L, R, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    pub fn new(
+        left_expression: EL,
+        right_expression: ER,
+        predicate: impl Fn(&L, &R) -> Result<bool, Err> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            left_expression,
+            right_expression,
+            predicate: Arc::new(predicate),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<L, R, Err, EL, ER> Clone for TrySemiJoin<L, R, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            left_expression: self.left_expression.clone(),
+            right_expression: self.right_expression.clone(),
+            predicate: self.predicate.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<L, R, Err, EL, ER> Expression<L> for TrySemiJoin<L, R, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq,
+    R: Clone + Eq + PartialEq,
+    Err: Error + Send + Sync + 'static,
+    EL: Expression<L>,
+    ER: Expression<R>,
+{
+    fn eval(&self) -> Vec<L> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<L>, EvalError> {
+        let left_result = self.left_expression.try_eval_with_context(context)?;
+        let right_result = self.right_expression.try_eval_with_context(context)?;
+
+        let to_eval_error = |error| EvalError::new("TrySemiJoin", error);
+        let mut result = Vec::new();
+
+        for row1 in left_result {
+            if matches(&*self.predicate, &row1, &right_result).map_err(to_eval_error)? {
+                result.push(row1);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Tests whether ``row1`` matches any of ``rows`` according to the fallible ``predicate``.
+pub(crate) fn matches<L, R, Err>(
+    predicate: &dyn Fn(&L, &R) -> Result<bool, Err>,
+    row1: &L,
+    rows: &[R],
+) -> Result<bool, Err> {
+    for row2 in rows {
+        if predicate(row1, row2)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+impl<L, R, Err, EL, ER> ParallelExpression<L> for TrySemiJoin<L, R, Err, EL, ER>
+where
+    L: Clone + Eq + PartialEq + Send + Sync,
+    R: Clone + Eq + PartialEq + Send + Sync,
+    Err: Error + Send + Sync + 'static,
+    EL: ParallelExpression<L>,
+    ER: ParallelExpression<R>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::num::ParseIntError;
+
+    #[test]
+    fn keep_matching_rows() {
+        let values1 = &[("1", "a"), ("2", "b")];
+        let values2 = &[2, 3];
+
+        assert_eq!(
+            TrySemiJoin::new(Terminal::new(values1), Terminal::new(values2), |x, y| {
+                Ok::<_, ParseIntError>(x.0.parse::<i32>()? == *y)
+            })
+            .try_eval()
+            .unwrap(),
+            &[("2", "b")]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values1 = &[("1", "a"), ("oops", "b")];
+        let values2 = &[2, 3];
+
+        let error = TrySemiJoin::new(Terminal::new(values1), Terminal::new(values2), |x, y| {
+            Ok::<_, ParseIntError>(x.0.parse::<i32>()? == *y)
+        })
+        .try_eval()
+        .unwrap_err();
+
+        assert_eq!(error.operator, "TrySemiJoin");
+    }
+}
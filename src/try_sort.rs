@@ -0,0 +1,129 @@
+use crate::{EvalContext, EvalError, Expression, ParallelExpression};
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// Orders the tuples in ``expression`` by the value of the fallible ``key`` from
+/// smallest to largest.
+///
+/// This is the fallible counterpart of ``Sort`` with an ``Asc`` order; wrapping the key
+/// in ``std::cmp::Reverse`` orders from largest to smallest. The key is computed once
+/// per tuple and the sort is stable. Evaluation with ``try_eval`` stops at the first
+/// error returned by ``key``. Calling ``eval`` panics if it fails.
+pub struct TrySort<S, K, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Ord,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub expression: E,
+    pub key: Arc<dyn Fn(&S) -> Result<K, Err> + Send + Sync>,
+}
+
+impl<S, K, Err, E> TrySort<S, K, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Ord,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    pub fn new(expression: E, key: impl Fn(&S) -> Result<K, Err> + Send + Sync + 'static) -> Self {
+        Self {
+            expression,
+            key: Arc::new(key),
+        }
+    }
+
+    /// Sorts the evaluated result of ``expression``.
+    fn sort(&self, rows: Vec<S>) -> Result<Vec<S>, Err> {
+        let mut keyed = rows
+            .into_iter()
+            .map(|row| Ok(((self.key)(&row)?, row)))
+            .collect::<Result<Vec<_>, Err>>()?;
+
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(keyed.into_iter().map(|(_, row)| row).collect())
+    }
+}
+
+impl<S, K, Err, E> Clone for TrySort<S, K, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Ord,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<S, K, Err, E> Expression<S> for TrySort<S, K, Err, E>
+where
+    S: Clone + Eq + PartialEq,
+    K: Ord,
+    Err: Error + Send + Sync + 'static,
+    E: Expression<S>,
+{
+    fn eval(&self) -> Vec<S> {
+        self.try_eval().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        self.sort(self.expression.try_eval_with_context(context)?)
+            .map_err(|error| EvalError::new("TrySort", error))
+    }
+}
+
+impl<S, K, Err, E> ParallelExpression<S> for TrySort<S, K, Err, E>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    K: Ord,
+    Err: Error + Send + Sync + 'static,
+    E: ParallelExpression<S>,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Terminal;
+
+    use std::cmp::Reverse;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn sort_by_parsed_key() {
+        let values = &[("10", "a"), ("9", "b"), ("10", "c")];
+
+        assert_eq!(
+            TrySort::new(Terminal::new(values), |x| x.0.parse::<i32>())
+                .try_eval()
+                .unwrap(),
+            &[("9", "b"), ("10", "a"), ("10", "c")]
+        );
+        assert_eq!(
+            TrySort::new(Terminal::new(values), |x| x.0.parse::<i32>().map(Reverse))
+                .try_eval()
+                .unwrap(),
+            &[("10", "a"), ("10", "c"), ("9", "b")]
+        );
+    }
+
+    #[test]
+    fn first_error_returned() {
+        let values = &[("10", "a"), ("oops", "b")];
+
+        let error = TrySort::new(Terminal::new(values), |x| x.0.parse::<i32>())
+            .try_eval()
+            .unwrap_err();
+
+        assert_eq!(error.operator, "TrySort");
+        assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+    }
+}
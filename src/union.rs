@@ -1,4 +1,7 @@
-use crate::Expression;
+use crate::parallel::par_join;
+use crate::{
+    Changes, Delta, EvalContext, EvalError, Expression, IncrementalExpression, ParallelExpression,
+};
 
 use std::marker::PhantomData;
 
@@ -46,6 +49,65 @@ where
         left_result.append(&mut right_result);
         left_result
     }
+
+    fn try_eval_with_context(&self, context: &EvalContext) -> Result<Vec<S>, EvalError> {
+        let mut left_result = (self.left_expression).try_eval_with_context(context)?;
+        let mut right_result = (self.right_expression).try_eval_with_context(context)?;
+
+        left_result.append(&mut right_result);
+        Ok(left_result)
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = S> + 'a>
+    where
+        S: 'a,
+    {
+        Box::new(self.left_expression.iter().chain(self.right_expression.iter()))
+    }
+}
+
+impl<S, E1, E2> ParallelExpression<S> for Union<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq + Send + Sync,
+    E1: ParallelExpression<S>,
+    E2: ParallelExpression<S>,
+{
+    /// Evaluates both sides concurrently.
+    fn par_eval(&self, threads: usize) -> Vec<S> {
+        let (mut left_result, mut right_result) = par_join(
+            threads,
+            |threads| self.left_expression.par_eval(threads),
+            |threads| self.right_expression.par_eval(threads),
+        );
+
+        left_result.append(&mut right_result);
+        left_result
+    }
+}
+
+impl<S, E1, E2> IncrementalExpression<S> for Union<S, E1, E2>
+where
+    S: Clone + Eq + PartialEq,
+    E1: IncrementalExpression<S>,
+    E2: IncrementalExpression<S>,
+{
+    type State = (E1::State, E2::State);
+
+    fn init(&self) -> Self::State {
+        (self.left_expression.init(), self.right_expression.init())
+    }
+
+    fn delta(&self, state: &mut Self::State, changes: &Changes) -> Delta<S> {
+        let mut delta = self.left_expression.delta(&mut state.0, changes);
+        delta.extend(self.right_expression.delta(&mut state.1, changes));
+
+        delta
+    }
+
+    fn apply(&mut self, changes: &Changes) {
+        self.left_expression.apply(changes);
+        self.right_expression.apply(changes);
+    }
 }
 
 #[cfg(test)]
@@ -74,3 +74,784 @@ fn complex_transformations() {
 
     assert_eq!(result, expected_result);
 }
+
+#[test]
+fn difference_transformations() {
+    let values1 = &[(1, "a"), (2, "b"), (2, "b"), (3, "c"), (3, "c")];
+    let values2 = &[(2, "b"), (3, "c")];
+
+    let bag_result = ExpressionBuilder::new(Terminal::new(values1))
+        .difference(values2)
+        .eval();
+    let set_result = ExpressionBuilder::new(Terminal::new(values1))
+        .except(values2)
+        .eval();
+
+    assert_eq!(bag_result, &[(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(set_result, &[(1, "a")]);
+}
+
+#[test]
+fn equi_join_transformations() {
+    let values1 = &[(1, "a"), (2, "b"), (3, "c")];
+    let values2 = &[("Joined", 1), ("Joined", 3), ("Joined", 3)];
+
+    let mut result = ExpressionBuilder::new(Terminal::new(values1))
+        .select(|x| x.0 > 1)
+        .equi_join(values2, |x| x.0, |y| y.1, |x, y| (x.1, y.0))
+        .eval();
+    result.sort();
+
+    assert_eq!(result, &[("c", "Joined"), ("c", "Joined")]);
+}
+
+#[test]
+fn outer_join_transformations() {
+    let values1 = &[(1, "a"), (2, "b")];
+    let values2 = &[(2, "x"), (3, "y")];
+
+    let left_result = ExpressionBuilder::new(Terminal::new(values1))
+        .left_join(values2, |x, y| x.0 == y.0, |x, y| (x.1, y.map(|y| y.1)))
+        .eval();
+    let right_result = ExpressionBuilder::new(Terminal::new(values1))
+        .right_join(values2, |x, y| x.0 == y.0, |x, y| (x.map(|x| x.1), y.1))
+        .eval();
+    let full_result = ExpressionBuilder::new(Terminal::new(values1))
+        .full_join(
+            values2,
+            |x, y| x.0 == y.0,
+            |x, y| (x.map(|x| x.1), y.map(|y| y.1)),
+        )
+        .eval();
+
+    assert_eq!(left_result, &[("a", None), ("b", Some("x"))]);
+    assert_eq!(right_result, &[(Some("b"), "x"), (None, "y")]);
+    assert_eq!(
+        full_result,
+        &[(Some("a"), None), (Some("b"), Some("x")), (None, Some("y"))]
+    );
+}
+
+#[test]
+fn semi_and_anti_join_transformations() {
+    let values1 = &[(1, "a"), (2, "b"), (3, "c")];
+    let values2 = &[2, 2, 3];
+
+    let semi_result = ExpressionBuilder::new(Terminal::new(values1))
+        .semi_join(values2, |x, y| x.0 == *y)
+        .eval();
+    let anti_result = ExpressionBuilder::new(Terminal::new(values1))
+        .anti_join(values2, |x, y| x.0 == *y)
+        .eval();
+
+    assert_eq!(semi_result, &[(2, "b"), (3, "c")]);
+    assert_eq!(anti_result, &[(1, "a")]);
+}
+
+#[test]
+fn group_by_transformations() {
+    let values = &[("a", 1), ("b", 2), ("a", 3), ("c", 4), ("b", 5)];
+
+    let result = ExpressionBuilder::new(Terminal::new(values))
+        .select(|x| x.0 != "c")
+        .group_by(|x| x.0, (Count, Sum::new(|x: &(&str, i32)| x.1)))
+        .project(|x| (x.0, x.1 .0, x.1 .1))
+        .eval();
+
+    assert_eq!(result, &[("a", 2, 4), ("b", 2, 7)]);
+}
+
+#[test]
+fn paging_transformations() {
+    let values = &[(3, "c"), (1, "a"), (5, "e"), (2, "b"), (4, "d"), (2, "a")];
+
+    let result = ExpressionBuilder::new(Terminal::new(values))
+        .order_by((Asc::new(|x: &(i32, &str)| x.0), Asc::new(|x: &(i32, &str)| x.1)))
+        .offset(1)
+        .limit(3)
+        .eval();
+
+    let reversed = ExpressionBuilder::new(Terminal::new(values))
+        .sort_by(|x, y| y.cmp(x))
+        .limit(2)
+        .eval();
+
+    assert_eq!(result, &[(2, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(reversed, &[(5, "e"), (4, "d")]);
+}
+
+#[test]
+fn distinct_transformations() {
+    let values = &[(1, "a"), (2, "b"), (1, "c"), (3, "d")];
+
+    let result = ExpressionBuilder::new(Terminal::new(values))
+        .project(|x| x.0)
+        .distinct()
+        .eval();
+
+    assert_eq!(result, &[1, 2, 3]);
+}
+
+#[test]
+fn binary_operator_multiplicities() {
+    // Tuple 1 occurs 3 times on the left and twice on the right,
+    // tuple 2 occurs only on the left and tuple 3 only on the right.
+    let left = &[1, 1, 1, 2];
+    let right = &[1, 1, 3];
+
+    let builder = ExpressionBuilder::new(Terminal::new(left));
+
+    assert_eq!(builder.union_all(right).eval(), &[1, 1, 1, 2, 1, 1, 3]);
+    assert_eq!(builder.union_distinct(right).eval(), &[1, 2, 3]);
+
+    assert_eq!(builder.intersect_all(right).eval(), &[1, 1]);
+    assert_eq!(builder.intersect_distinct(right).eval(), &[1]);
+
+    assert_eq!(builder.except_all(right).eval(), &[1, 2]);
+    assert_eq!(builder.except(right).eval(), &[2]);
+
+    assert_eq!(
+        builder.cartesian_product(right, |x, y| (*x, *y)).eval().len(),
+        left.len() * right.len()
+    );
+    assert_eq!(
+        builder.join(right, |x, y| x == y, |x, y| (*x, *y)).eval(),
+        &[(1, 1); 6]
+    );
+    assert_eq!(
+        builder
+            .cartesian_product(right, |x, y| (*x, *y))
+            .distinct()
+            .eval(),
+        &[(1, 1), (1, 3), (2, 1), (2, 3)]
+    );
+    assert_eq!(builder.semi_join(right, |x, y| x == y).eval(), &[1, 1, 1]);
+    assert_eq!(builder.anti_join(right, |x, y| x == y).eval(), &[2]);
+}
+
+#[test]
+fn division_transformations() {
+    let supplies = &[
+        ("s1", "p1", 10),
+        ("s1", "p2", 20),
+        ("s2", "p1", 30),
+        ("s2", "p2", 40),
+        ("s3", "p1", 50),
+    ];
+    let parts = &["p1", "p2"];
+
+    let result = ExpressionBuilder::new(Terminal::new(supplies))
+        .select(|x| x.2 > 10)
+        .divide(parts, |x| x.0, |x| x.1)
+        .eval();
+
+    assert_eq!(result, &["s2"]);
+}
+
+#[test]
+fn transitive_closure_transformations() {
+    let edges = &[("ceo", "cto"), ("cto", "lead"), ("lead", "dev"), ("ceo", "cfo")];
+
+    let mut result = ExpressionBuilder::new(Terminal::new(edges))
+        .transitive_closure()
+        .select(|x| x.0 == "ceo")
+        .project(|x| x.1)
+        .eval();
+    result.sort();
+
+    assert_eq!(result, &["cfo", "cto", "dev", "lead"]);
+}
+
+#[test]
+fn streaming_matches_eval() {
+    let values1 = &[(1, "a"), (2, "b"), (3, "c"), (2, "b")];
+    let values2 = &[(2, "x"), (3, "y"), (3, "z")];
+
+    let joined = ExpressionBuilder::new(Terminal::new(values1))
+        .select(|x| x.0 > 1)
+        .join(values2, |x, y| x.0 == y.0, |x, y| (x.1, y.1));
+    let outer = ExpressionBuilder::new(Terminal::new(values1))
+        .left_join(values2, |x, y| x.0 == y.0, |x, y| (x.1, y.map(|y| y.1)));
+    let hashed = ExpressionBuilder::new(Terminal::new(values1))
+        .equi_join(values2, |x| x.0, |y| y.0, |x, y| (x.1, y.1));
+    let set_ops = ExpressionBuilder::new(Terminal::new(values1))
+        .union(&[(4, "d")])
+        .except_all(&[(2, "b")])
+        .intersect(&[(2, "b"), (3, "c"), (4, "d")])
+        .distinct()
+        .offset(1)
+        .limit(5);
+
+    assert_eq!(joined.iter().collect::<Vec<_>>(), joined.eval());
+    assert_eq!(outer.iter().collect::<Vec<_>>(), outer.eval());
+    assert_eq!(hashed.iter().collect::<Vec<_>>(), hashed.eval());
+    assert_eq!(set_ops.iter().collect::<Vec<_>>(), set_ops.eval());
+    assert_eq!(set_ops.eval(), &[(2, "b"), (4, "d")]);
+}
+
+#[test]
+fn limit_stops_pulling_rows() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PULLED: AtomicUsize = AtomicUsize::new(0);
+
+    let values: Vec<i32> = (0..1000).collect();
+
+    let result = ExpressionBuilder::new(Terminal::new(&values))
+        .select(|x| {
+            PULLED.fetch_add(1, Ordering::SeqCst);
+            x % 2 == 0
+        })
+        .project(|x| x * 10)
+        .limit(3)
+        .eval();
+
+    assert_eq!(result, &[0, 20, 40]);
+    assert_eq!(PULLED.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn parallel_matches_sequential() {
+    let values1: Vec<(i32, i32)> = (0..20_000).map(|x| (x, x % 7)).collect();
+    let values2: Vec<(i32, &str)> = (0..7)
+        .map(|x| (x, if x % 2 == 0 { "even" } else { "odd" }))
+        .collect();
+    let values3: Vec<(i32, i32)> = (10_000..30_000).map(|x| (x, x % 7)).collect();
+
+    let query = ExpressionBuilder::new(Terminal::new(&values1))
+        .select(|x| x.0 % 3 != 0)
+        .union(&values3)
+        .intersect(&values1)
+        .equi_join(&values2, |x| x.1, |y| y.0, |x, y| (x.0, y.1))
+        .join(&values2, |x, y| x.1 == y.1, |x, y| (x.0, y.0))
+        .project(|x| (x.1, x.0 / 100))
+        .distinct()
+        .order_by((
+            Desc::new(|x: &(i32, i32)| x.1),
+            Asc::new(|x: &(i32, i32)| x.0),
+        ))
+        .offset(10);
+
+    let grouped = ExpressionBuilder::new(Terminal::new(&values1))
+        .cartesian_product(&values2, |x, y| (x.1, y.1))
+        .left_join(&values2, |x, y| x.0 == y.0, |x, y| (x.1, y.is_some()))
+        .group_by(|x| *x, Count);
+
+    let sequential = query.eval();
+
+    assert!(!sequential.is_empty());
+    for threads in [1, 2, 3, 8] {
+        assert_eq!(query.par_eval(threads), sequential);
+        assert_eq!(grouped.par_eval(threads), grouped.eval());
+    }
+}
+
+#[test]
+fn cached_transformations() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    let values = &[(1, "a"), (2, "b"), (3, "c")];
+
+    let cached = ExpressionBuilder::new(Terminal::new(values))
+        .select(|x| {
+            EVALUATIONS.fetch_add(1, Ordering::SeqCst);
+            x.0 > 1
+        })
+        .cache();
+    let query = cached.project(|x| x.1);
+
+    assert_eq!(query.eval(), &["b", "c"]);
+    assert_eq!(query.eval(), &["b", "c"]);
+    assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 3);
+
+    cached.invalidate();
+
+    assert_eq!(query.eval(), &["b", "c"]);
+    assert_eq!(EVALUATIONS.load(Ordering::SeqCst), 6);
+}
+
+#[test]
+fn shared_terminal_transformations() {
+    use std::sync::Arc;
+
+    let rows: Arc<[(i32, &str)]> = Arc::from(vec![(1, "a"), (2, "b"), (3, "c")]);
+
+    let query = ExpressionBuilder::new(Terminal::from_shared(rows.clone()))
+        .select(|x| x.0 > 1)
+        .project(|x| x.1);
+
+    assert_eq!(query.eval(), &["b", "c"]);
+    assert_eq!(query.iter().collect::<Vec<_>>(), &["b", "c"]);
+    assert_eq!(Arc::strong_count(&rows), 2);
+}
+
+#[test]
+fn fallible_transformations() {
+    use std::num::ParseIntError;
+
+    let values1 = &[("1", "a"), ("2", "b"), ("3", "c")];
+    let values2 = &[(2, "Joined"), (3, "Joined")];
+
+    let query = ExpressionBuilder::new(Terminal::new(values1))
+        .try_project(|x| Ok::<_, ParseIntError>((x.0.parse::<i32>()?, x.1)))
+        .try_select(|x| Ok::<_, ParseIntError>(x.0 > 1))
+        .try_join(
+            values2,
+            |x, y| Ok::<_, ParseIntError>(x.0 == y.0),
+            |x, y| Ok((x.1, y.1)),
+        )
+        .union(&[("d", "Union")])
+        .order_by(Asc::new(|x: &(&str, &str)| x.0));
+
+    assert_eq!(
+        query.try_eval().unwrap(),
+        &[("b", "Joined"), ("c", "Joined"), ("d", "Union")]
+    );
+
+    let failing = ExpressionBuilder::new(Terminal::new(&[("1", "a"), ("x", "b")]))
+        .try_project(|x| Ok::<_, ParseIntError>((x.0.parse::<i32>()?, x.1)))
+        .select(|x| x.0 > 0)
+        .distinct();
+
+    let error = failing.try_eval().unwrap_err();
+
+    assert_eq!(error.operator, "TryProjection");
+    assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[test]
+fn fallible_counterparts() {
+    use std::cmp::Reverse;
+    use std::num::ParseIntError;
+
+    fn parse(code: &str) -> Result<i32, ParseIntError> {
+        code.parse()
+    }
+
+    let codes = &[("1", "a"), ("2", "b"), ("3", "c"), ("2", "d")];
+    let names = &[(1, "x"), (2, "y")];
+    let query = ExpressionBuilder::new(Terminal::new(codes));
+
+    assert_eq!(
+        query
+            .try_equi_join(names, |x| parse(x.0), |y| Ok(y.0), |x, y| Ok((x.1, y.1)))
+            .try_order_by(|x| Ok::<_, ParseIntError>(Reverse(x.0)))
+            .try_eval()
+            .unwrap(),
+        &[("d", "y"), ("b", "y"), ("a", "x")]
+    );
+    assert_eq!(
+        query
+            .try_left_join(
+                names,
+                |x, y| Ok::<_, ParseIntError>(parse(x.0)? == y.0),
+                |x, y| Ok((x.1, y.map(|y| y.1))),
+            )
+            .try_eval()
+            .unwrap(),
+        &[("a", Some("x")), ("b", Some("y")), ("c", None), ("d", Some("y"))]
+    );
+    assert_eq!(
+        query
+            .try_right_join(
+                names,
+                |x, y| Ok::<_, ParseIntError>(parse(x.0)? == y.0),
+                |x, y| Ok((x.map(|x| x.1), y.1)),
+            )
+            .try_eval()
+            .unwrap(),
+        &[(Some("a"), "x"), (Some("b"), "y"), (Some("d"), "y")]
+    );
+    assert_eq!(
+        query
+            .try_full_join(
+                &[(3, "z"), (4, "w")],
+                |x, y| Ok::<_, ParseIntError>(parse(x.0)? == y.0),
+                |x, y| Ok((x.map(|x| x.1), y.map(|y| y.1))),
+            )
+            .try_eval()
+            .unwrap(),
+        &[
+            (Some("a"), None),
+            (Some("b"), None),
+            (Some("c"), Some("z")),
+            (Some("d"), None),
+            (None, Some("w")),
+        ]
+    );
+    assert_eq!(
+        query
+            .try_semi_join(names, |x, y| Ok::<_, ParseIntError>(parse(x.0)? == y.0))
+            .try_eval()
+            .unwrap(),
+        &[("1", "a"), ("2", "b"), ("2", "d")]
+    );
+    assert_eq!(
+        query
+            .try_anti_join(names, |x, y| Ok::<_, ParseIntError>(parse(x.0)? == y.0))
+            .try_eval()
+            .unwrap(),
+        &[("3", "c")]
+    );
+    assert_eq!(
+        query.try_group_by(|x| parse(x.0), Count).try_eval().unwrap(),
+        &[(1, 1), (2, 2), (3, 1)]
+    );
+    assert_eq!(
+        query.try_divide(&[2], |x| Ok(x.1), |x| parse(x.0)).try_eval().unwrap(),
+        &["b", "d"]
+    );
+
+    let error = ExpressionBuilder::new(Terminal::new(&[("x", "a")]))
+        .try_group_by(|x| parse(x.0), Count)
+        .try_eval()
+        .unwrap_err();
+
+    assert_eq!(error.operator, "TryGroupBy");
+    assert!(error.source.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[test]
+fn bounded_evaluation() {
+    let values: Vec<i32> = (0..1000).collect();
+
+    let query = ExpressionBuilder::new(Terminal::new(&values))
+        .cartesian_product(&values, |x, y| (*x, *y))
+        .select(|x| x.0 == x.1);
+
+    let error = query
+        .try_eval_with_context(&EvalContext::new().with_max_rows(10_000))
+        .unwrap_err();
+
+    assert_eq!(error.operator, "CartesianProduct");
+    assert_eq!(
+        error.source.downcast_ref::<LimitExceeded>(),
+        Some(&LimitExceeded::Rows(10_000))
+    );
+
+    let small = ExpressionBuilder::new(Terminal::new(&values[..10]))
+        .cartesian_product(&values[..10], |x, y| (*x, *y))
+        .select(|x| x.0 == x.1);
+
+    assert_eq!(
+        small
+            .try_eval_with_context(&EvalContext::new().with_max_rows(10_000))
+            .unwrap()
+            .len(),
+        10
+    );
+}
+
+#[test]
+fn capturing_closures() {
+    let threshold = 1;
+    let suffix = String::from("!");
+    let lookup = std::sync::Arc::new(vec![(threshold + 1, "Joined")]);
+
+    let query = ExpressionBuilder::new(Terminal::new(&[
+        (threshold, "a"),
+        (threshold + 1, "b"),
+        (threshold + 2, "c"),
+    ]))
+    .select(move |x| x.0 > threshold)
+    .join(
+        &[0],
+        move |x, _| lookup.iter().any(|y| y.0 == x.0),
+        |x, _| x.1,
+    )
+    .project(move |x| format!("{}{}", x, suffix));
+
+    assert_eq!(query.eval(), &[String::from("b!")]);
+    assert_eq!(query.par_eval(2), query.eval());
+}
+
+#[test]
+fn parameterised_plan() {
+    struct Params {
+        customer_id: i32,
+        discount: i32,
+    }
+
+    let orders = &[(1, "apples", 100), (2, "pears", 50), (1, "plums", 30)];
+    let customers = &[(1, "Alice"), (2, "Bob")];
+
+    let query = ExpressionBuilder::new(Terminal::new(orders))
+        .param_select(|x, params: &Params| x.0 == params.customer_id)
+        .param_project(|x, params: &Params| (x.0, x.1, x.2 - params.discount))
+        .param_join(
+            customers,
+            |x, y, _: &Params| x.0 == y.0,
+            |x, y, _| (y.1, x.1, x.2),
+        )
+        .order_by(Asc::new(|x: &(&str, &str, i32)| x.2));
+
+    assert_eq!(
+        query.eval_with(&Params {
+            customer_id: 1,
+            discount: 10
+        }),
+        &[("Alice", "plums", 20), ("Alice", "apples", 90)]
+    );
+    assert_eq!(
+        query.eval_with(&Params {
+            customer_id: 2,
+            discount: 0
+        }),
+        &[("Bob", "pears", 50)]
+    );
+
+    let error = query.try_eval().unwrap_err();
+    assert_eq!(error.operator, "ParamSelection");
+    assert!(error.source.downcast_ref::<MissingParams>().is_some());
+}
+
+#[test]
+fn maintained_view_matches_eval() {
+    let orders = Terminal::new(&[(1, "apples"), (2, "pears"), (3, "plums")]);
+
+    let query = ExpressionBuilder::new(orders.clone())
+        .select(|x| x.0 < 10)
+        .join(&[(1, "VIP")], |x, y| x.0 == y.0, |x, _| (x.0, x.1))
+        .union(&[(0, "gift")])
+        .project(|x| x.1)
+        .intersect(&["apples", "gift", "pears", "plums"]);
+
+    let mut view = query.maintain();
+
+    let mut changes = Changes::new();
+    changes.insert(
+        &orders,
+        Delta::new(vec![(1, "pears"), (1, "plums")], vec![(1, "apples")]),
+    );
+    view.update(&changes);
+
+    let mut changes = Changes::new();
+    changes.insert(&orders, Delta::new(vec![(1, "apples")], vec![(1, "plums")]));
+    let delta = view.update(&changes);
+
+    assert_eq!(delta.inserted, &["apples"]);
+    assert_eq!(delta.deleted, &["plums"]);
+
+    let mut result = view.result().to_vec();
+    let mut expected = view.expression().eval();
+    result.sort();
+    expected.sort();
+
+    assert_eq!(result, expected);
+    assert_eq!(result, &["apples", "gift", "pears"]);
+}
+
+#[test]
+fn spilling_operations() {
+    let orders: Vec<(u32, String)> = (0..400)
+        .map(|x| (x % 40, format!("order {}", x % 120)))
+        .collect();
+    let customers: Vec<(u32, String)> = (0..40).map(|x| (x, format!("customer {}", x))).collect();
+    let config = SpillConfig::new(16).with_partitions(4);
+
+    let query = ExpressionBuilder::new(Terminal::new(&orders))
+        .external_distinct(config.clone())
+        .external_equi_join(
+            &customers,
+            |x| x.0,
+            |y| y.0,
+            |x, y| (y.1.clone(), x.1.clone()),
+            config.clone(),
+        )
+        .external_order_by(
+            (
+                Asc::new(|x: &(String, String)| x.0.clone()),
+                Asc::new(|x: &(String, String)| x.1.clone()),
+            ),
+            config.clone(),
+        );
+
+    let expected = ExpressionBuilder::new(Terminal::new(&orders))
+        .distinct()
+        .equi_join(
+            &customers,
+            |x| x.0,
+            |y| y.0,
+            |x, y| (y.1.clone(), x.1.clone()),
+        )
+        .order_by((
+            Asc::new(|x: &(String, String)| x.0.clone()),
+            Asc::new(|x: &(String, String)| x.1.clone()),
+        ));
+
+    assert_eq!(query.eval(), expected.eval());
+    assert_eq!(query.try_eval().unwrap(), expected.eval());
+    assert!(config.spill_count() > 0);
+}
+
+#[test]
+fn bushy_plan() {
+    let students = &[(1, "Ann"), (2, "Bob"), (3, "Cat")];
+    let courses = &[(1, "Maths"), (2, "Physics"), (3, "Maths")];
+    let staff = &[(10, "Dan"), (11, "Eve")];
+    let modules = &[(10, "Chemistry"), (11, "Maths")];
+
+    let taking = ExpressionBuilder::new(Terminal::new(students)).join(
+        courses,
+        |x, y| x.0 == y.0,
+        |x, y| (x.1, y.1),
+    );
+    let teaching = ExpressionBuilder::new(Terminal::new(staff)).join(
+        modules,
+        |x, y| x.0 == y.0,
+        |x, y| (x.1, y.1),
+    );
+
+    let people = taking.union_with(teaching);
+
+    assert_eq!(
+        people.eval(),
+        &[
+            ("Ann", "Maths"),
+            ("Bob", "Physics"),
+            ("Cat", "Maths"),
+            ("Dan", "Chemistry"),
+            ("Eve", "Maths"),
+        ]
+    );
+
+    let maths = ExpressionBuilder::new(Terminal::new(&[("Maths", 1)]));
+    let result = people
+        .join_with(maths, |x, y| x.1 == y.0, |x, _| x.0)
+        .intersect_with(Terminal::new(&["Ann", "Eve", "Dan"]))
+        .cartesian_product_with(
+            Selection::new(Terminal::new(&[1, 2, 3]), |x| x % 2 == 1),
+            |x, y| (*x, *y),
+        );
+
+    assert_eq!(result.eval(), &[("Ann", 1), ("Ann", 3), ("Eve", 1), ("Eve", 3)]);
+}
+
+#[test]
+fn plan_chosen_at_runtime() {
+    let values = &[(1, "a"), (2, "b"), (3, "c")];
+
+    let plan = |filter: Option<i32>, limit: Option<usize>| {
+        let mut query = ExpressionBuilder::new(Terminal::new(values)).boxed();
+
+        if let Some(min) = filter {
+            query = query.select(move |x| x.0 >= min).boxed();
+        }
+        if let Some(count) = limit {
+            query = query.limit(count).boxed();
+        }
+
+        query.project(|x| x.1)
+    };
+
+    assert_eq!(plan(None, None).eval(), &["a", "b", "c"]);
+    assert_eq!(plan(Some(2), None).eval(), &["b", "c"]);
+    assert_eq!(plan(Some(2), Some(1)).eval(), &["b"]);
+    assert_eq!(plan(None, Some(2)).par_eval(2), &["a", "b"]);
+}
+
+#[test]
+fn relation_sources() {
+    use std::collections::{HashMap, HashSet};
+
+    let prices = HashMap::from([("apple", 3), ("pear", 5), ("plum", 2)]);
+    let stocked: HashSet<&str> = ["apple", "plum"].into_iter().collect();
+
+    let result = ExpressionBuilder::new(prices.into_relation())
+        .join_with(stocked.into_relation(), |x, y| x.0 == *y, |x, _| *x)
+        .join_with(Generator::range(1..=2), |_, _| true, |x, y| (x.0, x.1 * y))
+        .order_by(Asc::new(|x: &(&str, i32)| *x));
+
+    assert_eq!(
+        result.eval(),
+        &[("apple", 3), ("apple", 6), ("plum", 2), ("plum", 4)]
+    );
+
+    let evens: Terminal<u32> = (0..10).filter(|x| x % 2 == 0).collect();
+
+    assert_eq!(
+        ExpressionBuilder::new(evens)
+            .union_with(Terminal::from_vec(vec![1, 3]))
+            .eval(),
+        &[0, 2, 4, 6, 8, 1, 3]
+    );
+}
+
+#[test]
+fn textbook_notation() {
+    let students = Terminal::new(&[(1, "Ann"), (2, "Bob"), (3, "Cat")]);
+    let enrolments = Terminal::new(&[(1, "Maths"), (3, "Maths"), (3, "Physics")]);
+
+    let query = ra!(
+        project[|x| x.0](
+            students
+                join[|x, y| x.0 == y.0, |x, y| (x.1, y.1)]
+                select[|y| y.1 == "Maths"](enrolments)
+        )
+    );
+
+    let expected_result = ExpressionBuilder::new(students)
+        .join_with(
+            Selection::new(enrolments, |y| y.1 == "Maths"),
+            |x, y| x.0 == y.0,
+            |x, y| (x.1, y.1),
+        )
+        .project(|x| x.0);
+
+    assert_eq!(query.eval(), expected_result.eval());
+    assert_eq!(query.eval(), &["Ann", "Cat"]);
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Relation)]
+struct Course {
+    code: &'static str,
+    title: String,
+    credits: u8,
+}
+
+#[test]
+fn derived_relation_columns() {
+    let courses = Terminal::from_vec(vec![
+        Course {
+            code: "CS2",
+            title: String::from("Databases"),
+            credits: 20,
+        },
+        Course {
+            code: "CS1",
+            title: String::from("Programming"),
+            credits: 10,
+        },
+        Course {
+            code: "MA1",
+            title: String::from("Calculus"),
+            credits: 20,
+        },
+    ]);
+
+    let query = ExpressionBuilder::new(courses)
+        .select(|x| *Course::CREDITS.get(x) == 20)
+        .order_by(Course::CODE);
+
+    assert_eq!(
+        query.schema().to_string(),
+        "Course(code: &'static str, title: String, credits: u8)"
+    );
+    assert_eq!(
+        query.project_columns((Course::CODE, Course::TITLE)).eval(),
+        &[
+            ("CS2", String::from("Databases")),
+            ("MA1", String::from("Calculus"))
+        ]
+    );
+
+    let tuples = query.project(|x| x.clone().into_tuple());
+
+    assert_eq!(
+        tuples.project(|x| Course::from_tuple(x.clone())).eval(),
+        query.eval()
+    );
+    assert_eq!(tuples.eval()[0], ("CS2", String::from("Databases"), 20));
+}